@@ -1,23 +1,107 @@
-use mime;
+use crate::body::{self, MimeKind, MultipartPart};
+use crate::detect::{self, Finding};
+use crate::diff::{self, Change, EntryDiff, HeaderKind};
+use crate::filter::{Expr, ParseError};
+use crate::har;
+use crate::lenient::Warning;
+use crate::list;
+use crate::sanitize::{self, Mask, Rules};
+use crate::search::Query;
+use crate::snippet;
+use crate::stats::Stats;
+use crate::tui;
+use base64::Engine;
 use ratatui::{prelude::*, widgets::*};
-use url;
+use std::fmt;
 
 #[derive(Debug)]
 pub struct App {
     pub running: bool,
+    /// Position of the selected entry in `view`
     index: usize,
+    /// Indices of the entries shown in the table, in display order
+    view: Vec<usize>,
     pub har: Har,
-    //pub preview_widget_state: PreviewWidetState,
+    pub preview_widget_state: PreviewWidgetState,
+    /// Share of the screen given to the preview in the bottom and right layouts, in percent
+    pub preview_size: u16,
+    focus: Pane,
+    /// Scroll offsets of the preview, one per tab
+    preview_scrolls: [usize; TABBAR_ITEMS.len()],
+    /// The entry the scroll offsets belong to
+    preview_scroll_entry: Option<usize>,
+    /// Size of the panes as of the last render, used for paging
+    table_page_size: usize,
+    preview_page_size: usize,
+    /// Where the panes were drawn on the last render, used to handle mouse events
+    pub screen: ScreenLayout,
+    dragging_divider: bool,
     pub tabbar_state: TabBarState,
+    pub header_section: HeaderSection,
+    collapsed_header_sections: Vec<HeaderSection>,
+    pub main_view: MainView,
+    pub prompt: Option<Prompt>,
+    pub search: Option<Search>,
+    pub filter: Option<Filter>,
+    /// Indices of the entries marked to be saved, in `har.log.entries`
+    marked: Vec<usize>,
+    pub sort_column: Option<SortColumn>,
+    pub sort_descending: bool,
+    pub message: Option<String>,
+    /// Problems found while loading the file in lenient mode
+    pub warnings: Vec<Warning>,
+    /// Scroll offset of the warnings panel, `None` while it is closed
+    pub warnings_panel: Option<usize>,
+    /// Position of the selected format in the snippet picker, `None` while it is closed
+    pub snippet_picker: Option<usize>,
+    /// Statistics of the visible entries, `None` while the summary is closed
+    pub summary: Option<Summary>,
+    /// Whether each entry contains secrets or personal data, in `har.log.entries`
+    sensitive: Vec<bool>,
+    /// Names of the values masked by `redact`
+    redact_rules: Rules,
 }
 
 impl App {
-    pub fn init(har: Har) -> Self {
+    pub fn init(har: Har, warnings: Vec<Warning>, redact_rules: Rules) -> Self {
         Self {
             running: true,
             index: 0,
+            view: (0..har.log.entries.len()).collect(),
+            preview_widget_state: PreviewWidgetState::Bottom,
+            preview_size: PREVIEW_SIZE_DEFAULT,
+            focus: Pane::Table,
+            preview_scrolls: [0; TABBAR_ITEMS.len()],
+            preview_scroll_entry: None,
+            table_page_size: 1,
+            preview_page_size: 1,
+            screen: ScreenLayout::default(),
+            dragging_divider: false,
             tabbar_state: TabBarState::Headers,
-            har: har,
+            header_section: HeaderSection::General,
+            collapsed_header_sections: Vec::new(),
+            main_view: MainView::Table,
+            prompt: None,
+            search: None,
+            filter: None,
+            marked: Vec::new(),
+            sort_column: None,
+            sort_descending: false,
+            message: match warnings.len() {
+                0 => None,
+                1 => Some("1 warning while loading the file, press W to show it".to_string()),
+                count => Some(format!(
+                    "{} warnings while loading the file, press W to show them",
+                    count
+                )),
+            },
+            warnings,
+            warnings_panel: None,
+            snippet_picker: None,
+            summary: None,
+            sensitive: sensitive_entries(&har),
+            har,
+            redact_rules,
         }
     }
 
@@ -28,13 +112,194 @@ impl App {
     }
 
     pub fn max_index(&self) -> usize {
-        self.har.log.entries.len()
+        self.view.len()
+    }
+
+    /// Returns the index of the selected entry in `har.log.entries`
+    pub fn selected_entry_index(&self) -> Option<usize> {
+        self.view.get(self.index).copied()
+    }
+
+    pub fn view(&self) -> &[usize] {
+        &self.view
+    }
+
+    /// Returns the table items of the visible entries, in display order
+    pub fn table_items(&self) -> Vec<TableItem> {
+        let items = self.har.to_table_items();
+        self.view
+            .iter()
+            .map(|i| TableItem {
+                has_warnings: self.has_warnings(*i),
+                marked: self.marked.contains(i),
+                sensitive: self.sensitive[*i],
+                ..items[*i].clone()
+            })
+            .collect()
+    }
+
+    /// Marks or unmarks the selected entry, then moves to the next one
+    pub fn toggle_mark(&mut self) {
+        let Some(entry_index) = self.selected_entry_index() else {
+            return;
+        };
+        match self.marked.iter().position(|i| *i == entry_index) {
+            Some(position) => {
+                self.marked.remove(position);
+            }
+            None => self.marked.push(entry_index),
+        }
+        self.update_index(1);
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+
+    /// Writes the marked entries, or the visible entries if none is marked, to a new HAR file
+    fn save_entries(&mut self, path: &str) {
+        let (indices, description) = if self.marked.is_empty() {
+            (self.view.clone(), "visible")
+        } else {
+            (self.marked.clone(), "marked")
+        };
+
+        let har = self.har.with_entries(&indices);
+        self.message = Some(match har.to_new_file(std::path::Path::new(path)) {
+            Ok(()) => format!(
+                "Saved {} {} entries to {}",
+                har.log.entries.len(),
+                description,
+                path
+            ),
+            Err(err)
+                if err
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|err| err.kind() == std::io::ErrorKind::AlreadyExists) =>
+            {
+                format!("Not saved: {} already exists, choose another path", path)
+            }
+            Err(err) => format!("Failed to save {}: {}", path, err),
+        });
+    }
+
+    /// Masks secrets in the loaded entries with the built-in rules and those given with
+    /// `--rules`, so they can be saved and shared
+    pub fn redact(&mut self) {
+        let count = sanitize::sanitize(&mut self.har, &self.redact_rules, Mask::Redact);
+        self.sensitive = sensitive_entries(&self.har);
+        self.refresh_view();
+        if let Some(input) = self.search.as_ref().map(|search| search.input.clone()) {
+            self.set_search(&input);
+        }
+        self.message = Some(format!("Redacted {} values (save with Ctrl-S)", count));
+    }
+
+    /// Renders a snippet that reproduces the request of the selected entry
+    pub fn selected_snippet(&self, format: snippet::Format) -> Option<String> {
+        self.selected_entry_index()
+            .and_then(|index| self.har.log.entries.get(index))
+            .map(|entry| format.render(entry))
+    }
+
+    /// Copies a snippet that reproduces the request of the selected entry to the clipboard
+    pub fn copy_snippet(&mut self, format: snippet::Format) {
+        self.snippet_picker = None;
+        let Some(snippet) = self.selected_snippet(format) else {
+            return;
+        };
+
+        self.message = Some(match tui::copy_to_clipboard(&snippet) {
+            Ok(()) => format!("Copied the request as {} to the clipboard", format),
+            Err(err) => format!("Failed to copy to the clipboard: {}", err),
+        });
+    }
+
+    pub fn open_snippet_picker(&mut self) {
+        self.snippet_picker = Some(0);
+        self.message = None;
+    }
+
+    pub fn close_snippet_picker(&mut self) {
+        self.snippet_picker = None;
+    }
+
+    pub fn update_snippet_picker(&mut self, delta: i32) {
+        if let Some(position) = &mut self.snippet_picker {
+            let max = snippet::FORMATS.len() as i64 - 1;
+            *position = (*position as i64 + delta as i64).clamp(0, max) as usize;
+        }
+    }
+
+    /// Copies the snippet in the format selected in the picker
+    pub fn copy_picked_snippet(&mut self) {
+        if let Some(position) = self.snippet_picker {
+            self.copy_snippet(snippet::FORMATS[position]);
+        }
+    }
+
+    pub fn sensitive_count(&self) -> usize {
+        self.sensitive
+            .iter()
+            .filter(|sensitive| **sensitive)
+            .count()
+    }
+
+    pub fn has_warnings(&self, entry_index: usize) -> bool {
+        self.warnings
+            .iter()
+            .any(|warning| warning.entry == Some(entry_index))
+    }
+
+    pub fn toggle_warnings_panel(&mut self) {
+        self.warnings_panel = match self.warnings_panel {
+            Some(_) => None,
+            None => Some(0),
+        };
+        self.message = None;
+    }
+
+    pub fn update_warnings_scroll(&mut self, delta: i32) {
+        if let Some(scroll) = &mut self.warnings_panel {
+            // Each warning takes at least two lines
+            let max = (self.warnings.len() * 2).saturating_sub(1);
+            *scroll = (*scroll as i64 + delta as i64).clamp(0, max as i64) as usize;
+        }
+    }
+
+    /// Opens the summary of the visible entries, or closes it
+    pub fn toggle_summary(&mut self) {
+        self.summary = match self.summary {
+            Some(_) => None,
+            None => Some(Summary {
+                stats: Stats::new(&self.har, &self.view, SUMMARY_TOP_ENTRIES),
+                scroll: 0,
+            }),
+        };
+        self.message = None;
+    }
+
+    pub fn update_summary_scroll(&mut self, delta: i32) {
+        if let Some(summary) = &mut self.summary {
+            let max = summary
+                .stats
+                .sections()
+                .iter()
+                .map(|(_, lines)| lines.len() + 2)
+                .sum::<usize>()
+                .saturating_sub(1);
+            summary.scroll = (summary.scroll as i64 + delta as i64).clamp(0, max as i64) as usize;
+        }
     }
 
     pub fn update_index(&mut self, delta: i32) {
         let max = self.max_index();
         let added = self.index as i32 + delta;
-        self.index = if added < 0 {
+        self.index = if added < 0 || max == 0 {
             0
         } else if added >= max as i32 {
             max - 1
@@ -47,16 +312,426 @@ impl App {
     }
 
     pub fn update_index_last(&mut self) {
-        self.index = self.har.log.entries.len() - 1
+        self.index = self.max_index().saturating_sub(1)
+    }
+
+    fn select_entry(&mut self, entry_index: Option<usize>) {
+        self.index = entry_index
+            .and_then(|entry_index| self.view.iter().position(|i| *i == entry_index))
+            .unwrap_or(self.index.min(self.max_index().saturating_sub(1)));
+    }
+
+    /// Rebuilds `view` from the filter and the sort order, keeping the selected entry if it is
+    /// still visible
+    fn refresh_view(&mut self) {
+        let selected = self.selected_entry_index();
+        self.view = self.har.to_view(
+            self.filter.as_ref().map(|filter| &filter.expr),
+            self.sort_column,
+            self.sort_descending,
+        );
+        self.select_entry(selected);
+    }
+
+    /// Cycles the sort column, going back to the file order after the last one
+    pub fn cycle_sort_column(&mut self) {
+        self.sort_column = match self.sort_column {
+            None => Some(SORT_COLUMNS[0]),
+            Some(column) => SORT_COLUMNS.get(column.to_index() + 1).copied(),
+        };
+        self.refresh_view();
+    }
+
+    pub fn reverse_sort_order(&mut self) {
+        self.sort_descending = !self.sort_descending;
+        self.refresh_view();
+    }
+
+    pub fn cycle_preview_widget_state(&mut self) {
+        self.preview_widget_state = match self.preview_widget_state {
+            PreviewWidgetState::Bottom => PreviewWidgetState::Right,
+            PreviewWidgetState::Right => PreviewWidgetState::Full,
+            PreviewWidgetState::Full => PreviewWidgetState::Hidden,
+            PreviewWidgetState::Hidden => PreviewWidgetState::Bottom,
+        };
+    }
+
+    /// Returns the pane that receives the navigation keys.
+    /// Only one pane can be focused when the other is not visible.
+    pub fn focused_pane(&self) -> Pane {
+        match self.preview_widget_state {
+            PreviewWidgetState::Hidden => Pane::Table,
+            PreviewWidgetState::Full => Pane::Preview,
+            PreviewWidgetState::Bottom | PreviewWidgetState::Right => self.focus,
+        }
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focused_pane() {
+            Pane::Table => Pane::Preview,
+            Pane::Preview => Pane::Table,
+        };
+    }
+
+    pub fn preview_scroll(&self) -> usize {
+        self.preview_scrolls[self.tabbar_state.to_index()]
+    }
+
+    pub fn update_preview_scroll(&mut self, delta: i32) {
+        let scroll = &mut self.preview_scrolls[self.tabbar_state.to_index()];
+        *scroll = (*scroll as i64 + delta as i64).max(0) as usize;
+    }
+
+    pub fn update_preview_scroll_first(&mut self) {
+        self.preview_scrolls[self.tabbar_state.to_index()] = 0;
+    }
+
+    pub fn update_preview_scroll_last(&mut self) {
+        // Clamped to the end of the content on the next render
+        self.preview_scrolls[self.tabbar_state.to_index()] = usize::MAX;
+    }
+
+    /// Moves the focused pane by pages
+    pub fn update_page(&mut self, delta: i32) {
+        match self.focused_pane() {
+            Pane::Table => self.update_index(delta * self.table_page_size as i32),
+            Pane::Preview => self.update_preview_scroll(delta * self.preview_page_size as i32),
+        }
+    }
+
+    pub fn set_table_page_size(&mut self, size: usize) {
+        self.table_page_size = size.max(1);
+    }
+
+    /// Resets the scroll offsets when another entry has been selected
+    pub fn sync_preview_scroll(&mut self) {
+        let selected = self.selected_entry_index();
+        if self.preview_scroll_entry != selected {
+            self.preview_scroll_entry = selected;
+            self.preview_scrolls = [0; TABBAR_ITEMS.len()];
+        }
+    }
+
+    /// Records the size of the preview and clamps the scroll offset to its content
+    pub fn set_preview_viewport(&mut self, page_size: usize, content_height: usize) {
+        self.preview_page_size = page_size.max(1);
+        let scroll = &mut self.preview_scrolls[self.tabbar_state.to_index()];
+        *scroll = (*scroll).min(content_height.saturating_sub(page_size));
+    }
+
+    pub fn set_focus(&mut self, pane: Pane) {
+        self.focus = pane;
+    }
+
+    /// Selects the row at the given position of the table, if there is an entry
+    pub fn select_row(&mut self, row: usize) {
+        if row < self.max_index() {
+            self.index = row;
+        }
+    }
+
+    pub fn start_divider_drag(&mut self) {
+        self.dragging_divider = true;
+    }
+
+    pub fn end_divider_drag(&mut self) {
+        self.dragging_divider = false;
+    }
+
+    /// Resizes the preview so that the divider follows the mouse cursor
+    pub fn drag_divider(&mut self, column: u16, row: u16) {
+        if !self.dragging_divider {
+            return;
+        }
+
+        let main = self.screen.main;
+        let (position, length) = match self.preview_widget_state {
+            PreviewWidgetState::Bottom => (row.saturating_sub(main.y), main.height),
+            PreviewWidgetState::Right => (column.saturating_sub(main.x), main.width),
+            PreviewWidgetState::Hidden | PreviewWidgetState::Full => return,
+        };
+        if length == 0 {
+            return;
+        }
+
+        let preview_size = 100 - (position as u32 * 100 / length as u32).min(100) as u16;
+        self.preview_size = preview_size.clamp(PREVIEW_SIZE_MIN, PREVIEW_SIZE_MAX);
+    }
+
+    pub fn update_preview_size(&mut self, delta: i32) {
+        self.preview_size = (self.preview_size as i32 + delta)
+            .clamp(PREVIEW_SIZE_MIN as i32, PREVIEW_SIZE_MAX as i32)
+            as u16;
     }
 
-    //pub fn set_preview_widget_state(&mut self, state: &PreviewWidetState) {
-    //    self.preview_widget_state = state.clone();
-    //}
     pub fn set_tabbar_state(&mut self, state: &TabBarState) {
         self.tabbar_state = state.clone();
     }
 
+    pub fn toggle_main_view(&mut self) {
+        self.main_view = match self.main_view {
+            MainView::Table => MainView::Waterfall,
+            MainView::Waterfall => MainView::Table,
+        };
+    }
+
+    pub fn update_header_section(&mut self, delta: i32) {
+        let max = HEADER_SECTIONS.len() as i32;
+        let index = (self.header_section.to_index() as i32 + delta).clamp(0, max - 1);
+        self.header_section = HEADER_SECTIONS[index as usize];
+    }
+
+    /// Does nothing unless the Headers tab is shown, as the sections are not visible otherwise
+    pub fn toggle_header_section(&mut self) {
+        if !matches!(self.tabbar_state, TabBarState::Headers) {
+            return;
+        }
+        let section = self.header_section;
+        if self.is_header_section_collapsed(&section) {
+            self.collapsed_header_sections.retain(|s| *s != section);
+        } else {
+            self.collapsed_header_sections.push(section);
+        }
+    }
+
+    pub fn is_header_section_collapsed(&self, section: &HeaderSection) -> bool {
+        self.collapsed_header_sections.contains(section)
+    }
+
+    pub fn open_prompt(&mut self, kind: PromptKind) {
+        self.message = None;
+        self.prompt = Some(Prompt {
+            kind,
+            input: match kind {
+                PromptKind::Search => String::new(),
+                PromptKind::Save => DEFAULT_SAVE_PATH.to_string(),
+                PromptKind::Filter => self
+                    .filter
+                    .as_ref()
+                    .map(|filter| filter.input.clone())
+                    .unwrap_or_default(),
+            },
+            error: None,
+            origin_entry: self.selected_entry_index(),
+            origin_search: self.search.clone(),
+            origin_filter: self.filter.clone(),
+        });
+    }
+
+    pub fn prompt_push_str(&mut self, s: &str) {
+        if let Some(prompt) = &mut self.prompt {
+            // A prompt is a single line, so pasted line breaks become spaces
+            prompt
+                .input
+                .extend(s.chars().map(|c| if c.is_control() { ' ' } else { c }));
+            self.on_prompt_changed();
+        }
+    }
+
+    pub fn prompt_backspace(&mut self) {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.input.pop();
+            self.on_prompt_changed();
+        }
+    }
+
+    pub fn prompt_clear(&mut self) {
+        if let Some(prompt) = &mut self.prompt {
+            prompt.input.clear();
+            self.on_prompt_changed();
+        }
+    }
+
+    fn on_prompt_changed(&mut self) {
+        let Some(prompt) = &self.prompt else {
+            return;
+        };
+        let input = prompt.input.clone();
+        let origin_entry = prompt.origin_entry;
+
+        match prompt.kind {
+            PromptKind::Search => {
+                self.set_search(&input);
+                self.select_entry(origin_entry);
+                self.jump_to_match(true, true);
+            }
+            PromptKind::Filter => {
+                let error = self.set_filter(&input).err();
+                if let Some(prompt) = &mut self.prompt {
+                    prompt.error = error.map(|err| err.to_string());
+                }
+            }
+            PromptKind::Save => {}
+        }
+    }
+
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+
+        match prompt.kind {
+            PromptKind::Search => {
+                if prompt.input.is_empty() {
+                    self.search = prompt.origin_search;
+                    self.select_entry(prompt.origin_entry);
+                } else if self.search_position().is_some_and(|(_, count)| count == 0) {
+                    self.message = Some(format!("Pattern not found: {}", prompt.input));
+                }
+            }
+            PromptKind::Filter => {
+                if let Some(error) = prompt.error {
+                    self.filter = prompt.origin_filter;
+                    self.refresh_view();
+                    self.message = Some(format!("Invalid filter: {}", error));
+                }
+            }
+            PromptKind::Save => {
+                let path = prompt.input.trim();
+                if !path.is_empty() {
+                    self.save_entries(path);
+                }
+            }
+        }
+    }
+
+    pub fn cancel_prompt(&mut self) {
+        if let Some(prompt) = self.prompt.take() {
+            match prompt.kind {
+                PromptKind::Search => self.search = prompt.origin_search,
+                PromptKind::Filter => {
+                    self.filter = prompt.origin_filter;
+                    self.refresh_view();
+                }
+                PromptKind::Save => {}
+            }
+            self.select_entry(prompt.origin_entry);
+        }
+    }
+
+    fn set_search(&mut self, input: &str) {
+        if input.is_empty() {
+            self.search = None;
+            return;
+        }
+
+        let query = Query::new(input);
+        let matches = self
+            .har
+            .log
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| query.matches_entry(entry))
+            .map(|(i, _)| i)
+            .collect();
+
+        self.search = Some(Search {
+            input: input.to_string(),
+            query,
+            matches,
+        });
+    }
+
+    /// Returns the position of the selected entry among the visible matches (counted from 1)
+    /// and the number of the visible matches
+    pub fn search_position(&self) -> Option<(Option<usize>, usize)> {
+        let search = self.search.as_ref()?;
+        let mut position = None;
+        let mut count = 0;
+
+        for (i, entry_index) in self.view.iter().enumerate() {
+            if search.is_match(*entry_index) {
+                count += 1;
+                if i == self.index {
+                    position = Some(count);
+                }
+            }
+        }
+
+        Some((position, count))
+    }
+
+    /// Moves the focus to the nearest match in the given direction, wrapping around at the ends
+    fn jump_to_match(&mut self, forward: bool, include_current: bool) {
+        let Some(search) = &self.search else {
+            return;
+        };
+        let current = self.index;
+        let mut positions = self
+            .view
+            .iter()
+            .enumerate()
+            .filter(|(_, entry_index)| search.is_match(**entry_index))
+            .map(|(i, _)| i);
+
+        let next = if forward {
+            let positions: Vec<usize> = positions.collect();
+            positions
+                .iter()
+                .find(|i| {
+                    if include_current {
+                        **i >= current
+                    } else {
+                        **i > current
+                    }
+                })
+                .or(positions.first())
+                .copied()
+        } else {
+            let positions: Vec<usize> = positions.by_ref().rev().collect();
+            positions
+                .iter()
+                .find(|i| {
+                    if include_current {
+                        **i <= current
+                    } else {
+                        **i < current
+                    }
+                })
+                .or(positions.first())
+                .copied()
+        };
+
+        if let Some(next) = next {
+            self.index = next;
+        }
+    }
+
+    pub fn search_next(&mut self, forward: bool) {
+        match self.search_position() {
+            Some((_, 0)) => {
+                if let Some(search) = &self.search {
+                    self.message = Some(format!("Pattern not found: {}", search.input));
+                }
+            }
+            Some(_) => {
+                self.message = None;
+                self.jump_to_match(forward, false);
+            }
+            None => {}
+        }
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search = None;
+        self.message = None;
+    }
+
+    fn set_filter(&mut self, input: &str) -> Result<(), ParseError> {
+        self.filter = if input.trim().is_empty() {
+            None
+        } else {
+            Some(Filter {
+                input: input.to_string(),
+                expr: Expr::parse(input)?,
+            })
+        };
+        self.refresh_view();
+
+        Ok(())
+    }
+
     pub fn quit(&mut self) {
         self.running = false;
     }
@@ -65,15 +740,53 @@ impl App {
 type Har = crate::Har;
 
 impl Har {
+    /// Returns the indices of the entries matching the filter, in display order
+    pub fn to_view(
+        &self,
+        filter: Option<&Expr>,
+        sort_column: Option<SortColumn>,
+        descending: bool,
+    ) -> Vec<usize> {
+        let mut view: Vec<usize> = self
+            .log
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| filter.is_none_or(|filter| filter.matches(entry)))
+            .map(|(i, _)| i)
+            .collect();
+
+        match sort_column {
+            Some(column) => {
+                let items = self.to_table_items();
+                // The sort is stable, so entries with equal keys stay in file order
+                view.sort_by(|a, b| {
+                    let ordering = column.compare(&items[*a], &items[*b]);
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                });
+            }
+            None if descending => view.reverse(),
+            None => {}
+        }
+
+        view
+    }
+
     pub fn to_table_items(&self) -> Vec<TableItem> {
         self.log
             .entries
             .iter()
-            .map(|entry| {
+            .enumerate()
+            .map(|(index, entry)| {
                 let result = url::Url::parse(entry.request.url.as_str());
 
                 TableItem {
-                    status: entry.response.status as u16,
+                    index,
+                    status: u16::try_from(entry.response.status).unwrap_or(0),
                     method: entry.request.method.clone(),
                     domain: {
                         match result {
@@ -94,25 +807,95 @@ impl Har {
                         .clone()
                         .unwrap_or("".to_string()),
                     size: entry.response.content.size,
+                    time: entry.time,
+                    started: chrono::DateTime::parse_from_rfc3339(&entry.started_date_time).ok(),
+                    has_warnings: false,
+                    marked: false,
+                    sensitive: false,
                 }
             })
             .collect()
     }
 
+    pub fn to_waterfall(&self) -> Waterfall {
+        let started = |s: &str| chrono::DateTime::parse_from_rfc3339(s).ok();
+        let entry_starts: Vec<_> = self
+            .log
+            .entries
+            .iter()
+            .map(|entry| started(&entry.started_date_time))
+            .collect();
+        let origin = entry_starts.iter().flatten().min().copied();
+        let offset =
+            |datetime: Option<chrono::DateTime<chrono::FixedOffset>>| match (origin, datetime) {
+                (Some(origin), Some(datetime)) => {
+                    (datetime - origin).num_microseconds().unwrap_or(0) as f64 / 1000.0
+                }
+                _ => 0.0,
+            };
+
+        let items = self
+            .log
+            .entries
+            .iter()
+            .zip(entry_starts.iter())
+            .map(|(entry, start)| {
+                let timings = &entry.timings;
+                let phases = [
+                    (TimingPhase::Blocked, timings.blocked),
+                    (TimingPhase::Dns, timings.dns),
+                    (TimingPhase::Connect, timings.connect),
+                    (TimingPhase::Send, timings.send),
+                    (TimingPhase::Wait, timings.wait),
+                    (TimingPhase::Receive, timings.receive),
+                ]
+                .into_iter()
+                .filter_map(|(phase, duration)| Some((phase, duration.filter(|d| *d > 0.0)?)))
+                .collect();
+
+                WaterfallItem {
+                    offset: offset(*start),
+                    time: entry.time.max(0.0),
+                    phases,
+                }
+            })
+            .collect();
+
+        let markers = self
+            .log
+            .pages
+            .iter()
+            .flatten()
+            .flat_map(|page| {
+                let page_offset = offset(started(&page.started_date_time));
+                [
+                    (PageEvent::ContentLoad, page.page_timings.on_content_load),
+                    (PageEvent::Load, page.page_timings.on_load),
+                ]
+                .into_iter()
+                .filter_map(|(event, millis)| Some((event, millis.filter(|m| *m >= 0.0)?)))
+                .map(move |(event, millis)| (event, page_offset + millis))
+            })
+            .collect();
+
+        Waterfall { items, markers }
+    }
+
     pub fn to_header_info(&self, index: usize) -> Option<HeaderInfo> {
         if let Some(entry) = self.log.entries.get(index) {
             return Some(HeaderInfo {
                 status: entry.response.status,
+                status_text: entry.response.status_text.clone(),
                 method: entry.request.method.clone(),
                 http_version: entry.request.http_version.clone(),
                 url: entry.request.url.clone(),
                 referrer_policy: entry
-                    .request
+                    .response
                     .headers
                     .iter()
-                    .filter(|header| header.name.eq_ignore_ascii_case("Referrer-Policy"))
-                    .map(|header| header.value.clone())
-                    .next(),
+                    .chain(entry.request.headers.iter())
+                    .find(|header| header.name.eq_ignore_ascii_case("Referrer-Policy"))
+                    .map(|header| header.value.clone()),
                 query_params: entry
                     .request
                     .query_string
@@ -131,127 +914,546 @@ impl Har {
                     .iter()
                     .map(|header| (header.name.clone(), header.value.clone()))
                     .collect(),
+                cache: entry.cache.clone(),
+                comments: entry_comments(entry),
+                findings: detect::scan_entry(entry),
             });
         }
 
         None
     }
 
-    pub fn to_cookie_info(har: &Har, index: usize) -> Option<CookieInfo> {
-        if let Some(entry) = har.log.entries.get(index) {
+    pub fn to_cookie_info(&self, index: usize) -> Option<CookieInfo> {
+        if let Some(entry) = self.log.entries.get(index) {
             return Some(CookieInfo {
-                req_cookies: entry
-                    .request
-                    .cookies
-                    .iter()
-                    .map(|cookie| (cookie.name.clone(), cookie.value.clone()))
-                    .collect(),
-                resp_cookies: entry
-                    .response
-                    .cookies
-                    .iter()
-                    .map(|cookie| (cookie.name.clone(), cookie.value.clone()))
-                    .collect(),
+                req_cookies: entry.request.cookies.clone(),
+                resp_cookies: entry.response.cookies.clone(),
             });
         }
 
         None
     }
-}
-#[derive(Debug, Clone)]
-pub enum TabBarState {
-    Headers,
-    Cookies,
-    Request,
-    Response,
-}
 
-pub const TABBAR_ITEMS: [TabBarState; 4] = [
-    TabBarState::Headers,
-    TabBarState::Cookies,
-    TabBarState::Request,
-    TabBarState::Response,
-];
+    pub fn to_request_info(&self, index: usize) -> Option<RequestInfo> {
+        let post_data = self.log.entries.get(index)?.request.post_data.as_ref()?;
+        let params = post_data.params.clone().unwrap_or_default();
+        let text = post_data.text.clone().unwrap_or_default();
 
-impl TabBarState {
-    pub fn from_index(&self, index: usize) -> Option<Self> {
-        match index {
-            0 => Some(Self::Headers),
-            1 => Some(Self::Cookies),
-            2 => Some(Self::Request),
-            3 => Some(Self::Response),
-            _ => None,
-        }
-    }
+        let body = match MimeKind::from_mime_type(&post_data.mime_type) {
+            _ if text.is_empty() => RequestBody::Empty,
+            MimeKind::Json => match body::pretty_json(&text) {
+                Some(pretty) => RequestBody::Json(pretty),
+                None => RequestBody::Text(text),
+            },
+            MimeKind::FormUrlEncoded => RequestBody::Form(body::decode_form(&text)),
+            MimeKind::Multipart(Some(boundary)) => match body::split_multipart(&text, &boundary) {
+                Some(parts) => RequestBody::Multipart(parts),
+                None => RequestBody::Text(text),
+            },
+            _ => RequestBody::Text(text),
+        };
 
-    pub fn to_index(&self) -> usize {
-        match self {
-            Self::Headers => 0,
-            Self::Cookies => 1,
-            Self::Request => 2,
-            Self::Response => 3,
-        }
+        Some(RequestInfo {
+            mime_type: post_data.mime_type.clone(),
+            body_size: self.log.entries[index].request.body_size,
+            body,
+            params,
+        })
+    }
+
+    pub fn to_response_info(&self, index: usize) -> Option<ResponseInfo> {
+        let content = &self.log.entries.get(index)?.response.content;
+        let mime_type = content.mime_type.clone().unwrap_or_default();
+        let is_base64 = content
+            .encoding
+            .as_deref()
+            .is_some_and(|encoding| encoding.eq_ignore_ascii_case("base64"));
+
+        let body = match &content.text {
+            None => ResponseBody::Empty,
+            Some(text) if text.is_empty() => ResponseBody::Empty,
+            Some(text) if is_base64 => {
+                match base64::engine::general_purpose::STANDARD.decode(text.trim()) {
+                    Ok(bytes) => ResponseBody::from_bytes(&mime_type, bytes),
+                    Err(err) => ResponseBody::DecodeError(err.to_string()),
+                }
+            }
+            Some(text) => ResponseBody::from_bytes(&mime_type, text.as_bytes().to_vec()),
+        };
+
+        Some(ResponseInfo {
+            mime_type,
+            size: content.size,
+            compression: content.compression,
+            encoding: content.encoding.clone(),
+            body,
+        })
+    }
+
+    pub fn to_connection_info(&self, index: usize) -> Option<ConnectionInfo> {
+        let entry = self.log.entries.get(index)?;
+        let mut info = ConnectionInfo {
+            server_ip_address: entry.server_ipaddress.clone(),
+            connection: entry.connection.clone(),
+            security_state: entry.security_state.clone(),
+            ..Default::default()
+        };
+
+        if let Some(details) = &entry.security_details {
+            let datetime = |secs: Option<f64>| {
+                secs.and_then(|secs| chrono::DateTime::from_timestamp(secs as i64, 0))
+                    .map(|datetime| datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+            };
+
+            info.protocol = details.protocol.clone();
+            info.cipher = details.cipher.clone().map(|cipher| match &details.mac {
+                Some(mac) if !mac.is_empty() => format!("{} with {}", cipher, mac),
+                _ => cipher,
+            });
+            info.key_exchange = [&details.key_exchange, &details.key_exchange_group]
+                .into_iter()
+                .flatten()
+                .filter(|s| !s.is_empty())
+                .cloned()
+                .reduce(|a, b| format!("{} ({})", a, b));
+            info.subject = details.subject_name.clone();
+            info.san_list = details.san_list.clone().unwrap_or_default();
+            info.issuer = details.issuer.clone();
+            info.valid_from = datetime(details.valid_from);
+            info.valid_to = datetime(details.valid_to);
+        } else if let Some(security_info) = &entry.security_info {
+            let name = |name: &Option<har::CertificateName>| {
+                name.as_ref().and_then(|name| {
+                    [
+                        &name.common_name,
+                        &name.organization,
+                        &name.organizational_unit,
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .reduce(|a, b| format!("{}, {}", a, b))
+                })
+            };
+
+            if info.security_state.is_none() {
+                info.security_state = security_info.state.clone();
+            }
+            info.protocol = security_info.protocol_version.clone();
+            info.cipher = security_info.cipher_suite.clone();
+            info.key_exchange = security_info.kea_group_name.clone();
+            info.signature_scheme = security_info.signature_scheme_name.clone();
+            if let Some(cert) = &security_info.cert {
+                info.subject = name(&cert.subject);
+                info.issuer = name(&cert.issuer);
+                info.valid_from = cert.validity.as_ref().and_then(|v| v.start.clone());
+                info.valid_to = cert.validity.as_ref().and_then(|v| v.end.clone());
+            }
+        }
+
+        Some(info)
+    }
+
+    pub fn to_timing_info(&self, index: usize) -> Option<TimingInfo> {
+        let entry = self.log.entries.get(index)?;
+        let timings = &entry.timings;
+        // `-1` is used by HAR when a phase does not apply to the request
+        let phase = |value: Option<f64>| value.filter(|value| *value >= 0.0);
+
+        Some(TimingInfo {
+            started_date_time: chrono::DateTime::parse_from_rfc3339(&entry.started_date_time).ok(),
+            started_date_time_raw: entry.started_date_time.clone(),
+            time: entry.time,
+            phases: vec![
+                (TimingPhase::Blocked, phase(timings.blocked)),
+                (TimingPhase::Dns, phase(timings.dns)),
+                (TimingPhase::Connect, phase(timings.connect)),
+                (TimingPhase::Ssl, phase(timings.ssl)),
+                (TimingPhase::Send, phase(timings.send)),
+                (TimingPhase::Wait, phase(timings.wait)),
+                (TimingPhase::Receive, phase(timings.receive)),
+            ],
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum TabBarState {
+    Headers,
+    Cookies,
+    Request,
+    Response,
+    Timing,
+    Security,
+}
+
+pub const TABBAR_ITEMS: [TabBarState; 6] = [
+    TabBarState::Headers,
+    TabBarState::Cookies,
+    TabBarState::Request,
+    TabBarState::Response,
+    TabBarState::Timing,
+    TabBarState::Security,
+];
+
+impl TabBarState {
+    pub fn to_index(&self) -> usize {
+        match self {
+            Self::Headers => 0,
+            Self::Cookies => 1,
+            Self::Request => 2,
+            Self::Response => 3,
+            Self::Timing => 4,
+            Self::Security => 5,
+        }
+    }
+}
+
+impl fmt::Display for TabBarState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Headers => write!(f, " [1] Headers "),
+            Self::Cookies => write!(f, " [2] Cookies "),
+            Self::Request => write!(f, " [3] Request "),
+            Self::Response => write!(f, " [4] Response "),
+            Self::Timing => write!(f, " [5] Timing "),
+            Self::Security => write!(f, " [6] Security "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    Search,
+    Filter,
+    Save,
+}
+
+const DEFAULT_SAVE_PATH: &str = "harview-export.har";
+
+impl PromptKind {
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Self::Search => "/",
+            Self::Filter => "filter: ",
+            Self::Save => "save as: ",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Prompt {
+    pub kind: PromptKind,
+    pub input: String,
+    pub error: Option<String>,
+    origin_entry: Option<usize>,
+    origin_search: Option<Search>,
+    origin_filter: Option<Filter>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub input: String,
+    pub expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+pub struct Search {
+    pub input: String,
+    pub query: Query,
+    /// Indices of the matched entries in ascending order
+    pub matches: Vec<usize>,
+}
+
+impl Search {
+    pub fn is_match(&self, index: usize) -> bool {
+        self.matches.binary_search(&index).is_ok()
+    }
+}
+
+/// Number of the slowest and largest entries listed in the summary
+const SUMMARY_TOP_ENTRIES: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub stats: Stats,
+    pub scroll: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MainView {
+    Table,
+    Waterfall,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum SortColumn {
+    Status,
+    Method,
+    Domain,
+    FileName,
+    ContentType,
+    Size,
+    Time,
+    Started,
+}
+
+pub const SORT_COLUMNS: [SortColumn; 8] = [
+    SortColumn::Status,
+    SortColumn::Method,
+    SortColumn::Domain,
+    SortColumn::FileName,
+    SortColumn::ContentType,
+    SortColumn::Size,
+    SortColumn::Time,
+    SortColumn::Started,
+];
+
+impl SortColumn {
+    pub fn to_index(self) -> usize {
+        match self {
+            Self::Status => 0,
+            Self::Method => 1,
+            Self::Domain => 2,
+            Self::FileName => 3,
+            Self::ContentType => 4,
+            Self::Size => 5,
+            Self::Time => 6,
+            Self::Started => 7,
+        }
+    }
+
+    fn compare(self, a: &TableItem, b: &TableItem) -> std::cmp::Ordering {
+        match self {
+            Self::Status => a.status.cmp(&b.status),
+            Self::Method => a.method.cmp(&b.method),
+            Self::Domain => a.domain.cmp(&b.domain),
+            Self::FileName => a.file_name.cmp(&b.file_name),
+            Self::ContentType => a.mime_type.cmp(&b.mime_type),
+            Self::Size => a.size.cmp(&b.size),
+            Self::Time => a.time.total_cmp(&b.time),
+            Self::Started => a.started.cmp(&b.started),
+        }
+    }
+}
+
+impl fmt::Display for SortColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Status => "Status",
+            Self::Method => "Method",
+            Self::Domain => "Domain",
+            Self::FileName => "FileName",
+            Self::ContentType => "ContentType",
+            Self::Size => "Size",
+            Self::Time => "Time",
+            Self::Started => "Started",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeaderSection {
+    General,
+    Sensitive,
+    QueryParams,
+    RequestHeaders,
+    ResponseHeaders,
+    Cache,
+    Comments,
+}
+
+pub const HEADER_SECTIONS: [HeaderSection; 7] = [
+    HeaderSection::General,
+    HeaderSection::Sensitive,
+    HeaderSection::QueryParams,
+    HeaderSection::RequestHeaders,
+    HeaderSection::ResponseHeaders,
+    HeaderSection::Cache,
+    HeaderSection::Comments,
+];
+
+impl HeaderSection {
+    pub fn to_index(self) -> usize {
+        match self {
+            Self::General => 0,
+            Self::Sensitive => 1,
+            Self::QueryParams => 2,
+            Self::RequestHeaders => 3,
+            Self::ResponseHeaders => 4,
+            Self::Cache => 5,
+            Self::Comments => 6,
+        }
     }
 }
 
-impl ToString for TabBarState {
-    fn to_string(&self) -> String {
+impl fmt::Display for HeaderSection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Self::Headers => " [1] Headers ".to_string(),
-            Self::Cookies => " [2] Cookies ".to_string(),
-            Self::Request => " [3] Request ".to_string(),
-            Self::Response => " [4] Response ".to_string(),
+            Self::General => write!(f, "General"),
+            Self::Sensitive => write!(f, "Sensitive Data"),
+            Self::QueryParams => write!(f, "Query String Parameters"),
+            Self::RequestHeaders => write!(f, "Request Headers"),
+            Self::ResponseHeaders => write!(f, "Response Headers"),
+            Self::Cache => write!(f, "Cache"),
+            Self::Comments => write!(f, "Comments"),
         }
     }
 }
 
-//#[derive(Debug, Clone)]
-//pub enum PreviewWidetState {
-//    Hidden,
-//    Bottom,
-//    Right,
-//    Full,
-//}
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PreviewWidgetState {
+    Hidden,
+    Bottom,
+    Right,
+    Full,
+}
+
+#[derive(Debug, Default)]
+pub struct ScreenLayout {
+    /// The area shared by the table and the preview
+    pub main: Rect,
+    pub table: Rect,
+    /// The area of the table rows, excluding borders and headers
+    pub table_rows: Rect,
+    /// Position of the entry shown on the first row of the table
+    pub table_offset: usize,
+    pub preview: Rect,
+    /// Tabs of the preview and the columns they span on the tab bar
+    pub tabs: Vec<(TabBarState, u16, u16)>,
+    pub tabbar_row: u16,
+}
+
+impl ScreenLayout {
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<Pane> {
+        if rect_contains(self.table, column, row) {
+            Some(Pane::Table)
+        } else if rect_contains(self.preview, column, row) {
+            Some(Pane::Preview)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if the cell is on the border between the table and the preview
+    pub fn is_divider(&self, column: u16, row: u16) -> bool {
+        if self.table.is_empty() || self.preview.is_empty() {
+            return false;
+        }
+
+        if self.preview.x == self.table.x {
+            row + 1 == self.table.bottom() && self.table.left() <= column
+        } else {
+            column + 1 == self.table.right() && self.table.top() <= row
+        }
+    }
+
+    /// Returns the table row (counted from the top of the view) at the given cell
+    pub fn table_row_at(&self, column: u16, row: u16) -> Option<usize> {
+        if rect_contains(self.table_rows, column, row) {
+            Some(self.table_offset + (row - self.table_rows.y) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<TabBarState> {
+        if row != self.tabbar_row || self.preview.is_empty() {
+            return None;
+        }
+
+        self.tabs
+            .iter()
+            .find(|(_, start, end)| (*start..*end).contains(&column))
+            .map(|(state, _, _)| state.clone())
+    }
+}
+
+fn rect_contains(rect: Rect, column: u16, row: u16) -> bool {
+    rect.left() <= column && column < rect.right() && rect.top() <= row && row < rect.bottom()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pane {
+    Table,
+    Preview,
+}
+
+pub const PREVIEW_SIZE_DEFAULT: u16 = 50;
+pub const PREVIEW_SIZE_MIN: u16 = 20;
+pub const PREVIEW_SIZE_MAX: u16 = 80;
 
 #[derive(Debug, Clone)]
 pub struct TableItem {
+    index: usize,
     status: u16,
     method: String,
     domain: String,
     file_name: String,
     mime_type: String,
     size: Option<i64>,
+    time: f64,
+    started: Option<chrono::DateTime<chrono::FixedOffset>>,
+    has_warnings: bool,
+    marked: bool,
+    sensitive: bool,
 }
 
 impl TableItem {
-    pub fn to_table_row(&self) -> ratatui::widgets::Row<'static> {
-        let status_span = match self.status {
-            100..=199 => Span::styled(
-                self.status.to_string(),
-                Style::default().fg(Color::LightBlue).bold(),
-            ),
-            200..=299 => Span::styled(
-                self.status.to_string(),
-                Style::default().fg(Color::LightGreen).bold(),
-            ),
-            300..=399 => Span::styled(
-                self.status.to_string(),
-                Style::default().fg(Color::LightCyan).bold(),
-            ),
-            400..=499 => Span::styled(
-                self.status.to_string(),
-                Style::default().fg(Color::LightYellow).bold(),
-            ),
-            500..=599 => Span::styled(
-                self.status.to_string(),
-                Style::default().fg(Color::LightMagenta).bold(),
-            ),
-            0 => Span::styled("---", Style::default().fg(Color::DarkGray).bold()),
-            _ => Span::styled(
-                self.status.to_string(),
-                Style::default().bg(Color::DarkGray),
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn to_record(&self) -> list::Record {
+        list::Record {
+            entry: self.index + 1,
+            status: self.status,
+            method: self.method.clone(),
+            domain: self.domain.clone(),
+            file_name: self.file_name.clone(),
+            content_type: self.mime_type.clone(),
+            size: self.size,
+            time: (self.time >= 0.0).then_some(self.time),
+            started: self
+                .started
+                .map(|started| started.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+        }
+    }
+
+    pub fn to_label_spans(&self) -> Vec<Span<'static>> {
+        vec![
+            status_span(self.status),
+            Span::raw(" "),
+            Span::styled(
+                format!("{:<6} ", self.method),
+                Style::default().fg(Color::White).bold(),
             ),
+            Span::raw(format!("{}{}", self.domain, self.file_name)),
+        ]
+    }
+
+    pub fn to_table_row(&self, search: Option<&Search>) -> ratatui::widgets::Row<'static> {
+        let query = search.map(|search| &search.query);
+        let mark = if self.marked {
+            Span::styled("+", Style::default().fg(Color::LightGreen).bold())
+        } else {
+            Span::raw(" ")
         };
+        let sensitive = if self.sensitive {
+            Span::styled("◆", Style::default().fg(Color::LightMagenta))
+        } else {
+            Span::raw(" ")
+        };
+        let marker = match search {
+            Some(search) if search.is_match(self.index) => {
+                Span::styled("●", Style::default().fg(Color::Yellow))
+            }
+            _ if self.has_warnings => Span::styled("!", Style::default().fg(Color::LightRed)),
+            _ => Span::raw(" "),
+        };
+
+        let status_span = status_span(self.status);
 
         let mime_type = self.mime_type.clone();
         let shorten_mime = match mime_type.as_str().parse::<mime::Mime>() {
@@ -276,49 +1478,634 @@ impl TableItem {
             None => Span::styled("     --- B", Style::default().fg(Color::DarkGray)),
         };
 
+        let time_span = if self.time >= 0.0 {
+            Span::raw(format!("{:>7.0} ms", self.time))
+        } else {
+            Span::styled("    --- ms", Style::default().fg(Color::DarkGray))
+        };
+
         Row::new([
+            Cell::new(Line::from(vec![mark, sensitive, marker])),
             Cell::new(status_span),
-            Cell::new(Span::styled(
-                self.method.clone(),
+            Cell::new(highlight_matches(
+                &self.method,
                 Style::default().fg(Color::White).bold(),
+                query,
             )),
-            Cell::new(Span::styled(
-                self.domain.clone(),
+            Cell::new(highlight_matches(
+                &self.domain,
                 Style::default().fg(Color::White),
+                query,
             )),
-            Cell::new(self.file_name.clone()),
-            Cell::new(shorten_mime),
+            Cell::new(highlight_matches(&self.file_name, Style::default(), query)),
+            Cell::new(highlight_matches(&shorten_mime, Style::default(), query)),
             Cell::new(size_span),
+            Cell::new(time_span),
         ])
     }
 }
 
-pub const TABLES_ROWS_COUNT: usize = 6;
+/// Splits the text into spans so that occurrences of the query stand out
+pub fn highlight_matches(text: &str, style: Style, query: Option<&Query>) -> Line<'static> {
+    let ranges = query
+        .map(|query| query.find_ranges(text))
+        .unwrap_or_default();
+    let mut spans = Vec::new();
+    let mut last = 0;
 
-#[derive(Debug)]
-pub enum TabItem {
-    Headers,
-    Cookie,
-    Request,
-    Response,
-    Timing,
-    Encryption,
+    for (start, end) in ranges {
+        if start > last {
+            spans.push(Span::styled(text[last..start].to_string(), style));
+        }
+        spans.push(Span::styled(
+            text[start..end].to_string(),
+            style.fg(Color::Black).bg(Color::Yellow),
+        ));
+        last = end;
+    }
+    if last < text.len() || spans.is_empty() {
+        spans.push(Span::styled(text[last..].to_string(), style));
+    }
+
+    Line::from(spans)
+}
+
+pub fn status_span(status: u16) -> Span<'static> {
+    match status {
+        100..=199 => Span::styled(
+            status.to_string(),
+            Style::default().fg(Color::LightBlue).bold(),
+        ),
+        200..=299 => Span::styled(
+            status.to_string(),
+            Style::default().fg(Color::LightGreen).bold(),
+        ),
+        300..=399 => Span::styled(
+            status.to_string(),
+            Style::default().fg(Color::LightCyan).bold(),
+        ),
+        400..=499 => Span::styled(
+            status.to_string(),
+            Style::default().fg(Color::LightYellow).bold(),
+        ),
+        500..=599 => Span::styled(
+            status.to_string(),
+            Style::default().fg(Color::LightMagenta).bold(),
+        ),
+        0 => Span::styled("---", Style::default().fg(Color::DarkGray).bold()),
+        _ => Span::styled(status.to_string(), Style::default().bg(Color::DarkGray)),
+    }
 }
 
+pub const TABLES_ROWS_COUNT: usize = 8;
+
 #[derive(Debug)]
 pub struct HeaderInfo {
-    status: i64,
-    method: String,
-    http_version: String,
-    url: url::Url,
-    query_params: Vec<(String, String)>,
-    referrer_policy: Option<String>,
-    req_headers: Vec<(String, String)>,
-    resp_headers: Vec<(String, String)>,
+    pub status: i64,
+    pub status_text: String,
+    pub method: String,
+    pub http_version: String,
+    pub url: url::Url,
+    pub query_params: Vec<(String, String)>,
+    pub referrer_policy: Option<String>,
+    pub req_headers: Vec<(String, String)>,
+    pub resp_headers: Vec<(String, String)>,
+    pub cache: har::Cache,
+    /// Comments of the entry and its parts, keyed by where they are found
+    pub comments: Vec<(String, String)>,
+    pub findings: Vec<Finding>,
 }
 
 #[derive(Debug)]
 pub struct CookieInfo {
-    req_cookies: Vec<(String, String)>,
-    resp_cookies: Vec<(String, String)>,
+    pub req_cookies: Vec<har::Cookie>,
+    pub resp_cookies: Vec<har::Cookie>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CookieIssue {
+    MissingSecure,
+    MissingHttpOnly,
+    SameSiteNoneWithoutSecure,
+}
+
+impl fmt::Display for CookieIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingSecure => write!(f, "no Secure"),
+            Self::MissingHttpOnly => write!(f, "no HttpOnly"),
+            Self::SameSiteNoneWithoutSecure => write!(f, "SameSite=None without Secure"),
+        }
+    }
+}
+
+/// Whether each entry contains credentials or personal data found by `detect`
+fn sensitive_entries(har: &Har) -> Vec<bool> {
+    har.log
+        .entries
+        .iter()
+        .map(|entry| !detect::scan_entry(entry).is_empty())
+        .collect()
+}
+
+/// Collects the comments of the entry and the objects it contains
+fn entry_comments(entry: &har::Entry) -> Vec<(String, String)> {
+    let request = &entry.request;
+    let response = &entry.response;
+    let named = |location: &str, name: &str| format!("{}[{}]", location, name);
+
+    let mut comments: Vec<(String, Option<&String>)> = vec![
+        ("entry".to_string(), entry.comment.as_ref()),
+        ("request".to_string(), request.comment.as_ref()),
+    ];
+    comments.extend(request.headers.iter().map(|header| {
+        (
+            named("request.headers", &header.name),
+            header.comment.as_ref(),
+        )
+    }));
+    comments.extend(request.cookies.iter().map(|cookie| {
+        (
+            named("request.cookies", &cookie.name),
+            cookie.comment.as_ref(),
+        )
+    }));
+    comments.extend(request.query_string.iter().map(|query| {
+        (
+            named("request.queryString", &query.name),
+            query.comment.as_ref(),
+        )
+    }));
+    if let Some(post_data) = &request.post_data {
+        comments.push(("request.postData".to_string(), post_data.comment.as_ref()));
+        comments.extend(post_data.params.iter().flatten().map(|param| {
+            (
+                named("request.postData.params", &param.name),
+                param.comment.as_ref(),
+            )
+        }));
+    }
+    comments.push(("response".to_string(), response.comment.as_ref()));
+    comments.extend(response.headers.iter().map(|header| {
+        (
+            named("response.headers", &header.name),
+            header.comment.as_ref(),
+        )
+    }));
+    comments.extend(response.cookies.iter().map(|cookie| {
+        (
+            named("response.cookies", &cookie.name),
+            cookie.comment.as_ref(),
+        )
+    }));
+    comments.push((
+        "response.content".to_string(),
+        response.content.comment.as_ref(),
+    ));
+    comments.push(("cache".to_string(), entry.cache.comment.as_ref()));
+    comments.extend([
+        (
+            "cache.beforeRequest".to_string(),
+            entry
+                .cache
+                .before_request
+                .as_ref()
+                .and_then(|cache| cache.comment.as_ref()),
+        ),
+        (
+            "cache.afterRequest".to_string(),
+            entry
+                .cache
+                .after_request
+                .as_ref()
+                .and_then(|cache| cache.comment.as_ref()),
+        ),
+        ("timings".to_string(), entry.timings.comment.as_ref()),
+    ]);
+
+    comments
+        .into_iter()
+        .filter_map(|(location, comment)| {
+            Some((location, comment.filter(|c| !c.is_empty())?.clone()))
+        })
+        .collect()
+}
+
+/// Checks the attributes of a cookie set by the server (a `Set-Cookie` entry)
+pub fn cookie_issues(cookie: &har::Cookie) -> Vec<CookieIssue> {
+    let secure = cookie.secure.unwrap_or(false);
+    let mut issues = Vec::new();
+
+    if !secure {
+        issues.push(CookieIssue::MissingSecure);
+    }
+    if !cookie.http_only.unwrap_or(false) {
+        issues.push(CookieIssue::MissingHttpOnly);
+    }
+    if !secure
+        && cookie
+            .same_site
+            .as_deref()
+            .is_some_and(|same_site| same_site.eq_ignore_ascii_case("None"))
+    {
+        issues.push(CookieIssue::SameSiteNoneWithoutSecure);
+    }
+
+    issues
+}
+
+#[derive(Debug)]
+pub struct RequestInfo {
+    pub mime_type: String,
+    pub body_size: i64,
+    pub body: RequestBody,
+    pub params: Vec<har::Param>,
+}
+
+#[derive(Debug)]
+pub enum RequestBody {
+    Empty,
+    Json(String),
+    Form(Vec<(String, String)>),
+    Multipart(Vec<MultipartPart>),
+    Text(String),
+}
+
+#[derive(Debug)]
+pub struct ResponseInfo {
+    pub mime_type: String,
+    pub size: Option<i64>,
+    pub compression: Option<i64>,
+    pub encoding: Option<String>,
+    pub body: ResponseBody,
+}
+
+#[derive(Debug)]
+pub enum ResponseBody {
+    Empty,
+    Json(String),
+    Markup(String),
+    Text(String),
+    Binary(Vec<u8>),
+    DecodeError(String),
+}
+
+impl ResponseBody {
+    fn from_bytes(mime_type: &str, bytes: Vec<u8>) -> Self {
+        if !body::is_text(&bytes) {
+            return Self::Binary(bytes);
+        }
+
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+        match MimeKind::from_mime_type(mime_type) {
+            MimeKind::Json => match body::pretty_json(&text) {
+                Some(pretty) => Self::Json(pretty),
+                None => Self::Text(text),
+            },
+            MimeKind::Markup => Self::Markup(body::indent_markup(&text)),
+            _ => Self::Text(text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimingPhase {
+    Blocked,
+    Dns,
+    Connect,
+    Ssl,
+    Send,
+    Wait,
+    Receive,
+}
+
+impl fmt::Display for TimingPhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Blocked => write!(f, "Blocked"),
+            Self::Dns => write!(f, "DNS"),
+            Self::Connect => write!(f, "Connect"),
+            Self::Ssl => write!(f, "SSL"),
+            Self::Send => write!(f, "Send"),
+            Self::Wait => write!(f, "Wait"),
+            Self::Receive => write!(f, "Receive"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TimingInfo {
+    pub started_date_time: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub started_date_time_raw: String,
+    pub time: f64,
+    /// Phases in the order they happen; `None` means the phase is not applicable
+    pub phases: Vec<(TimingPhase, Option<f64>)>,
+}
+
+#[derive(Debug, Default)]
+pub struct ConnectionInfo {
+    pub server_ip_address: Option<String>,
+    pub connection: Option<String>,
+    pub security_state: Option<String>,
+    pub protocol: Option<String>,
+    pub cipher: Option<String>,
+    pub key_exchange: Option<String>,
+    pub signature_scheme: Option<String>,
+    pub subject: Option<String>,
+    pub san_list: Vec<String>,
+    pub issuer: Option<String>,
+    pub valid_from: Option<String>,
+    pub valid_to: Option<String>,
+}
+
+impl ConnectionInfo {
+    pub fn has_tls_details(&self) -> bool {
+        self.protocol.is_some() || self.cipher.is_some() || self.subject.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageEvent {
+    ContentLoad,
+    Load,
+}
+
+impl fmt::Display for PageEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ContentLoad => write!(f, "DOMContentLoaded"),
+            Self::Load => write!(f, "Load"),
+        }
+    }
+}
+
+/// Every entry placed on a time axis shared by the whole log.
+/// All values are in milliseconds from the start of the earliest entry.
+#[derive(Debug)]
+pub struct Waterfall {
+    pub items: Vec<WaterfallItem>,
+    pub markers: Vec<(PageEvent, f64)>,
+}
+
+impl Waterfall {
+    pub fn end(&self) -> f64 {
+        self.items
+            .iter()
+            .map(|item| item.offset + item.time.max(item.phases_time()))
+            .chain(self.markers.iter().map(|(_, millis)| *millis))
+            .fold(0.0, f64::max)
+    }
+}
+
+#[derive(Debug)]
+pub struct WaterfallItem {
+    pub offset: f64,
+    pub time: f64,
+    pub phases: Vec<(TimingPhase, f64)>,
+}
+
+impl WaterfallItem {
+    pub fn phases_time(&self) -> f64 {
+        self.phases.iter().map(|(_, duration)| duration).sum()
+    }
+}
+
+/// State of `harview diff --tui`, which shows two captures side by side
+#[derive(Debug)]
+pub struct DiffApp {
+    pub running: bool,
+    /// Paths of the earlier and the later file
+    pub paths: [String; 2],
+    before: Har,
+    after: Har,
+    diffs: Vec<EntryDiff>,
+    /// Indices of the shown diffs, without the unchanged entries unless `show_unchanged`
+    view: Vec<usize>,
+    /// Position of the selected diff in `view`
+    index: usize,
+    pub show_unchanged: bool,
+    pub detail_scroll: usize,
+    table_page_size: usize,
+    /// Where the side-by-side panes were drawn on the last render, used to handle mouse events
+    pub detail_area: Rect,
+    /// Smallest change of the total time highlighted as a difference, in milliseconds
+    min_time_delta: f64,
+}
+
+/// A field of the compared entries, with its value in each file
+#[derive(Debug, Clone)]
+pub struct ComparedField {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub differs: bool,
+}
+
+impl DiffApp {
+    pub fn init(
+        paths: [String; 2],
+        before: Har,
+        after: Har,
+        diffs: Vec<EntryDiff>,
+        options: &diff::Options,
+    ) -> Self {
+        let mut app = Self {
+            running: true,
+            paths,
+            before,
+            after,
+            diffs,
+            view: Vec::new(),
+            index: 0,
+            show_unchanged: false,
+            detail_scroll: 0,
+            table_page_size: 1,
+            detail_area: Rect::default(),
+            min_time_delta: options.min_time_delta,
+        };
+        app.refresh_view();
+        app
+    }
+
+    pub fn quit(&mut self) {
+        self.running = false;
+    }
+
+    fn refresh_view(&mut self) {
+        self.view = self
+            .diffs
+            .iter()
+            .enumerate()
+            .filter(|(_, diff)| self.show_unchanged || diff.change != Change::Unchanged)
+            .map(|(i, _)| i)
+            .collect();
+        self.index = self.index.min(self.view.len().saturating_sub(1));
+        self.detail_scroll = 0;
+    }
+
+    /// The shown diffs, in display order
+    pub fn rows(&self) -> Vec<&EntryDiff> {
+        self.view.iter().map(|i| &self.diffs[*i]).collect()
+    }
+
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn selected(&self) -> Option<&EntryDiff> {
+        self.view.get(self.index).map(|i| &self.diffs[*i])
+    }
+
+    pub fn count(&self, change: Change) -> usize {
+        self.diffs
+            .iter()
+            .filter(|diff| diff.change == change)
+            .count()
+    }
+
+    pub fn update_index(&mut self, delta: i32) {
+        let max = self.view.len().saturating_sub(1) as i64;
+        let index = (self.index as i64 + delta as i64).clamp(0, max) as usize;
+        if index != self.index {
+            self.index = index;
+            self.detail_scroll = 0;
+        }
+    }
+
+    pub fn update_index_first(&mut self) {
+        self.update_index(-(self.index as i32));
+    }
+
+    pub fn update_index_last(&mut self) {
+        self.update_index(self.view.len() as i32);
+    }
+
+    pub fn update_page(&mut self, delta: i32) {
+        self.update_index(delta * self.table_page_size.max(1) as i32);
+    }
+
+    pub fn set_table_page_size(&mut self, size: usize) {
+        self.table_page_size = size;
+    }
+
+    pub fn update_detail_scroll(&mut self, delta: i32) {
+        let max = self
+            .compared_fields()
+            .iter()
+            .map(|(_, fields)| fields.len() + 2)
+            .sum::<usize>()
+            .saturating_sub(1);
+        self.detail_scroll =
+            (self.detail_scroll as i64 + delta as i64).clamp(0, max as i64) as usize;
+    }
+
+    pub fn is_over_detail(&self, column: u16, row: u16) -> bool {
+        rect_contains(self.detail_area, column, row)
+    }
+
+    pub fn toggle_unchanged(&mut self) {
+        let selected = self.view.get(self.index).copied();
+        self.show_unchanged = !self.show_unchanged;
+        self.refresh_view();
+        if let Some(position) = selected.and_then(|i| self.view.iter().position(|j| *j == i)) {
+            self.index = position;
+        }
+    }
+
+    /// The fields of the selected entries by section, for showing them side by side
+    pub fn compared_fields(&self) -> Vec<(&'static str, Vec<ComparedField>)> {
+        let Some(diff) = self.selected() else {
+            return Vec::new();
+        };
+        let before = diff
+            .before
+            .as_ref()
+            .map(|side| &self.before.log.entries[side.index()]);
+        let after = diff
+            .after
+            .as_ref()
+            .map(|side| &self.after.log.entries[side.index()]);
+        let matched = before.is_some() && after.is_some();
+
+        let field = |name: &str, value: fn(&har::Entry) -> String, differs: bool| ComparedField {
+            name: name.to_string(),
+            before: before.map(value),
+            after: after.map(value),
+            differs: matched && differs,
+        };
+        let general = vec![
+            field(
+                "Status",
+                |entry| format!("{} {}", entry.response.status, entry.response.status_text),
+                diff.status_changed(),
+            ),
+            field(
+                "Size",
+                |entry| match entry.response.content.size {
+                    Some(size) => body::format_bytes(size),
+                    None => "---".to_string(),
+                },
+                diff.size_delta.is_some_and(|delta| delta != 0),
+            ),
+            field(
+                "Time",
+                |entry| format!("{:.0} ms", entry.time),
+                diff.time_delta
+                    .is_some_and(|delta| delta.abs() >= self.min_time_delta),
+            ),
+            field(
+                "MIME Type",
+                |entry| entry.response.content.mime_type.clone().unwrap_or_default(),
+                false,
+            ),
+            field(
+                "HTTP Version",
+                |entry| entry.response.http_version.clone(),
+                false,
+            ),
+            field("Started", |entry| entry.started_date_time.clone(), false),
+        ];
+
+        let headers = |kind: HeaderKind| {
+            let headers = |entry: &har::Entry| match kind {
+                HeaderKind::Request => entry.request.headers.clone(),
+                HeaderKind::Response => entry.response.headers.clone(),
+            };
+            let before = before.map(headers).unwrap_or_default();
+            let after = after.map(headers).unwrap_or_default();
+            let mut names: Vec<String> = Vec::new();
+            for header in before.iter().chain(after.iter()) {
+                let name = header.name.to_ascii_lowercase();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            let value = |headers: &[har::Header], name: &str| {
+                let values: Vec<&str> = headers
+                    .iter()
+                    .filter(|header| header.name.eq_ignore_ascii_case(name))
+                    .map(|header| header.value.as_str())
+                    .collect();
+                (!values.is_empty()).then(|| values.join("\n"))
+            };
+            names
+                .into_iter()
+                .map(|name| ComparedField {
+                    before: value(&before, &name),
+                    after: value(&after, &name),
+                    differs: diff
+                        .headers
+                        .iter()
+                        .any(|change| change.kind == kind && change.name == name),
+                    name,
+                })
+                .collect::<Vec<ComparedField>>()
+        };
+
+        vec![
+            ("General", general),
+            ("Request Headers", headers(HeaderKind::Request)),
+            ("Response Headers", headers(HeaderKind::Response)),
+        ]
+    }
 }
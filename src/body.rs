@@ -0,0 +1,294 @@
+use crate::har::Content;
+use base64::Engine;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MimeKind {
+    Json,
+    FormUrlEncoded,
+    Multipart(Option<String>),
+    Markup,
+    Text,
+    Other,
+}
+
+impl MimeKind {
+    pub fn from_mime_type(mime_type: &str) -> Self {
+        let mime = match mime_type.trim().parse::<mime::Mime>() {
+            Ok(mime) => mime,
+            Err(_) => return Self::Other,
+        };
+
+        if mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON) {
+            return Self::Json;
+        }
+
+        if mime.subtype() == mime::XML
+            || mime.subtype() == mime::HTML
+            || mime.suffix() == Some(mime::XML)
+        {
+            return Self::Markup;
+        }
+
+        match (mime.type_(), mime.subtype()) {
+            (mime::APPLICATION, mime::WWW_FORM_URLENCODED) => Self::FormUrlEncoded,
+            (mime::MULTIPART, mime::FORM_DATA) => Self::Multipart(
+                mime.get_param(mime::BOUNDARY)
+                    .map(|boundary| boundary.to_string()),
+            ),
+            (mime::TEXT, _) => Self::Text,
+            (mime::APPLICATION, subtype) if subtype.as_str().ends_with("javascript") => Self::Text,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MultipartPart {
+    pub name: Option<String>,
+    pub file_name: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+pub fn pretty_json(text: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    serde_json::to_string_pretty(&value).ok()
+}
+
+pub fn decode_form(text: &str) -> Vec<(String, String)> {
+    url::form_urlencoded::parse(text.trim().as_bytes())
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect()
+}
+
+/// Splits a `multipart/form-data` body into its parts.
+/// Returns `None` when the text does not contain the boundary.
+pub fn split_multipart(text: &str, boundary: &str) -> Option<Vec<MultipartPart>> {
+    let delimiter = format!("--{}", boundary);
+    let mut sections = text.split(delimiter.as_str());
+
+    // Everything before the first delimiter is the preamble
+    sections.next()?;
+
+    let parts: Vec<MultipartPart> = sections
+        .take_while(|section| !section.starts_with("--"))
+        .map(parse_multipart_part)
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts)
+    }
+}
+
+/// Parses a part of a `multipart/form-data` body, the text between two delimiters
+pub fn parse_multipart_part(section: &str) -> MultipartPart {
+    let section = section
+        .strip_prefix("\r\n")
+        .or_else(|| section.strip_prefix('\n'))
+        .unwrap_or(section);
+    let (head, body) = match section.split_once("\r\n\r\n") {
+        Some(split) => split,
+        None => section.split_once("\n\n").unwrap_or(("", section)),
+    };
+    let body = body
+        .strip_suffix("\r\n")
+        .or_else(|| body.strip_suffix('\n'))
+        .unwrap_or(body);
+
+    let headers: Vec<(String, String)> = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let disposition = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Content-Disposition"))
+        .map(|(_, value)| value.as_str())
+        .unwrap_or("");
+
+    MultipartPart {
+        name: disposition_param(disposition, "name"),
+        file_name: disposition_param(disposition, "filename"),
+        headers,
+        body: body.to_string(),
+    }
+}
+
+fn disposition_param(disposition: &str, key: &str) -> Option<String> {
+    disposition
+        .split(';')
+        .filter_map(|param| param.trim().split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case(key))
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+}
+
+/// Formats a size in bytes with a decimal unit, e.g. `1.50 MB`.
+/// Negative sizes, which HAR files use for unknown ones, count as zero.
+pub fn format_bytes(bytes: i64) -> String {
+    let b = byte_unit::Byte::from_u64(bytes.max(0) as u64);
+    let unit = b.get_appropriate_unit(byte_unit::UnitType::Decimal);
+    format!("{:.2} {}", unit.get_value(), unit.get_unit())
+}
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Re-indents XML or HTML markup, putting each tag and text node on its own line.
+/// The contents of `<script>` and `<style>` elements are kept as they are.
+pub fn indent_markup(text: &str) -> String {
+    let indent = |depth: usize| "  ".repeat(depth);
+    let mut lines: Vec<String> = Vec::new();
+    let mut depth: usize = 0;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let content = rest[..end].trim();
+            if !content.is_empty() {
+                lines.push(format!("{}{}", indent(depth), content));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|i| i + 3)
+        } else {
+            rest.find('>').map(|i| i + 1)
+        }
+        .unwrap_or(rest.len());
+        let tag = &rest[..end];
+        rest = &rest[end..];
+
+        let name = tag
+            .trim_start_matches(['<', '/'])
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+
+        if tag.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            lines.push(format!("{}{}", indent(depth), tag));
+        } else if tag.starts_with("<!")
+            || tag.starts_with("<?")
+            || tag.ends_with("/>")
+            || VOID_ELEMENTS.contains(&name.as_str())
+        {
+            lines.push(format!("{}{}", indent(depth), tag));
+        } else if let Some((content, closing)) = text_only_element(rest, &name) {
+            // Keep elements that only contain text on a single line
+            lines.push(format!(
+                "{}{}{}{}",
+                indent(depth),
+                tag,
+                content.trim(),
+                closing
+            ));
+            rest = &rest[content.len() + closing.len()..];
+        } else if name == "script" || name == "style" {
+            lines.push(format!("{}{}", indent(depth), tag));
+            let closing = format!("</{}", name);
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&closing)
+                .unwrap_or(rest.len());
+            lines.extend(
+                rest[..end]
+                    .lines()
+                    .map(|line| line.trim())
+                    .filter(|line| !line.is_empty())
+                    .map(|line| format!("{}{}", indent(depth + 1), line)),
+            );
+            rest = &rest[end..];
+            depth += 1;
+        } else {
+            lines.push(format!("{}{}", indent(depth), tag));
+            depth += 1;
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn text_only_element<'a>(rest: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    let content_end = rest.find('<')?;
+    let after = &rest[content_end..];
+    let closing_end = after.find('>')? + 1;
+    let closing = &after[..closing_end];
+
+    let closing_name = closing.strip_prefix("</")?.trim_end_matches('>').trim();
+    if closing_name.eq_ignore_ascii_case(name) {
+        Some((&rest[..content_end], closing))
+    } else {
+        None
+    }
+}
+
+/// Formats bytes like `hexdump -C`
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = (0..16)
+                .map(|j| match chunk.get(j) {
+                    Some(byte) => format!("{:02x}", byte),
+                    None => "  ".to_string(),
+                })
+                .collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|byte| {
+                    if byte.is_ascii_graphic() || *byte == b' ' {
+                        *byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            format!(
+                "{:08x}  {}  {}  |{}|",
+                i * 16,
+                hex[..8].join(" "),
+                hex[8..].join(" "),
+                ascii
+            )
+        })
+        .collect()
+}
+
+/// The text of a response body, decoded first if it is base64-encoded.
+/// Returns `None` if there is no body, or if a decoded body is not text.
+pub fn content_text(content: &Content) -> Option<String> {
+    let text = content.text.as_ref()?;
+    let is_base64 = content
+        .encoding
+        .as_deref()
+        .is_some_and(|encoding| encoding.eq_ignore_ascii_case("base64"));
+    if !is_base64 {
+        return Some(text.clone());
+    }
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(text.trim())
+        .ok()?;
+    is_text(&bytes).then(|| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Returns true if the bytes look like human readable text
+pub fn is_text(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => !s
+            .chars()
+            .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')),
+        Err(_) => false,
+    }
+}
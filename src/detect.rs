@@ -0,0 +1,297 @@
+//! Detection of secrets and personal data in entries.
+//!
+//! Values are matched against a fixed set of [`Rule`]s, so they can be flagged in the table
+//! and highlighted in the preview while browsing.
+
+use crate::body;
+use crate::har::{Cookie, Entry, Header};
+use base64::Engine;
+use regex::Regex;
+use std::fmt;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    Jwt,
+    AwsAccessKey,
+    BearerToken,
+    BasicAuth,
+    SessionCookie,
+    Email,
+    CardNumber,
+}
+
+/// Rules matched against values, in order of precedence when matches overlap
+const VALUE_RULES: [Rule; 6] = [
+    Rule::BearerToken,
+    Rule::BasicAuth,
+    Rule::Jwt,
+    Rule::AwsAccessKey,
+    Rule::CardNumber,
+    Rule::Email,
+];
+
+static JWT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\beyJ[A-Za-z0-9_-]{2,}\.eyJ[A-Za-z0-9_-]{2,}\.[A-Za-z0-9_-]*").unwrap()
+});
+static AWS_ACCESS_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b").unwrap());
+static BEARER_TOKEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bbearer\s+[A-Za-z0-9._~+/-]{16,}=*").unwrap());
+static BASIC_AUTH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bbasic\s+([A-Za-z0-9+/]{4,}={0,2})").unwrap());
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}\b").unwrap()
+});
+/// Numbers with the prefix of a major card network, optionally grouped with spaces or dashes
+static CARD_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:4|5[1-5]|2[2-7]|3[47]|6011|65)\d{0,3}(?:[ -]?\d){9,15}\b").unwrap()
+});
+static SESSION_COOKIE_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)sess|^sid$|[._-]sid$|auth|token|jwt|remember").unwrap());
+
+impl Rule {
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Jwt => "JSON Web Token, usually an access or ID token",
+            Self::AwsAccessKey => "AWS access key ID",
+            Self::BearerToken => "Bearer token that authenticates the request",
+            Self::BasicAuth => "Base64-encoded user name and password",
+            Self::SessionCookie => "Cookie named like a session or authentication cookie",
+            Self::Email => "Email address",
+            Self::CardNumber => "Number with a card network prefix and a valid Luhn checksum",
+        }
+    }
+
+    fn regex(self) -> &'static Regex {
+        match self {
+            Self::Jwt => &JWT,
+            Self::AwsAccessKey => &AWS_ACCESS_KEY,
+            Self::BearerToken => &BEARER_TOKEN,
+            Self::BasicAuth => &BASIC_AUTH,
+            Self::SessionCookie => &SESSION_COOKIE_NAME,
+            Self::Email => &EMAIL,
+            Self::CardNumber => &CARD_NUMBER,
+        }
+    }
+
+    /// Rejects matches that only look like the value
+    fn validate(self, matched: &str) -> bool {
+        match self {
+            Self::BasicAuth => BASIC_AUTH
+                .captures(matched)
+                .and_then(|captures| {
+                    base64::engine::general_purpose::STANDARD
+                        .decode(&captures[1])
+                        .ok()
+                })
+                .is_some_and(|decoded| decoded.contains(&b':')),
+            Self::CardNumber => luhn(matched),
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Jwt => write!(f, "JWT"),
+            Self::AwsAccessKey => write!(f, "AWS access key"),
+            Self::BearerToken => write!(f, "Bearer token"),
+            Self::BasicAuth => write!(f, "Basic credentials"),
+            Self::SessionCookie => write!(f, "Session cookie"),
+            Self::Email => write!(f, "Email address"),
+            Self::CardNumber => write!(f, "Card number"),
+        }
+    }
+}
+
+fn luhn(number: &str) -> bool {
+    let digits: Vec<u32> = number.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, digit)| match (i % 2 == 1, digit * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => *digit,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+/// A sensitive value found in an entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub rule: Rule,
+    /// Where the value was found, e.g. `request header authorization`
+    pub location: String,
+    pub value: String,
+}
+
+pub fn is_session_cookie(name: &str) -> bool {
+    SESSION_COOKIE_NAME.is_match(name)
+}
+
+/// Returns the byte ranges of sensitive values in the text, without overlaps
+pub fn find_ranges(text: &str) -> Vec<(usize, usize, Rule)> {
+    let mut ranges: Vec<(usize, usize, Rule)> = Vec::new();
+
+    for rule in VALUE_RULES {
+        for matched in rule.regex().find_iter(text) {
+            let overlaps = ranges
+                .iter()
+                .any(|(start, end, _)| matched.start() < *end && *start < matched.end());
+            if !overlaps && rule.validate(matched.as_str()) {
+                ranges.push((matched.start(), matched.end(), rule));
+            }
+        }
+    }
+
+    ranges.sort_by_key(|(start, _, _)| *start);
+    ranges
+}
+
+/// Like [`find_ranges`], but also finds the values of session cookies
+/// in `Cookie` and `Set-Cookie` headers
+pub fn find_header_ranges(name: &str, value: &str) -> Vec<(usize, usize, Rule)> {
+    let cookies = if name.eq_ignore_ascii_case("cookie") {
+        cookie_pairs(value, false)
+    } else if name.eq_ignore_ascii_case("set-cookie") {
+        cookie_pairs(value, true)
+    } else {
+        return find_ranges(value);
+    };
+
+    let mut ranges: Vec<(usize, usize, Rule)> = cookies
+        .into_iter()
+        .filter(|(name, start, end)| start < end && is_session_cookie(name))
+        .map(|(_, start, end)| (start, end, Rule::SessionCookie))
+        .collect();
+    for range in find_ranges(value) {
+        if !ranges
+            .iter()
+            .any(|(start, end, _)| range.0 < *end && *start < range.1)
+        {
+            ranges.push(range);
+        }
+    }
+
+    ranges.sort_by_key(|(start, _, _)| *start);
+    ranges
+}
+
+/// Returns the name and the byte range of the value of each cookie in a header.
+/// In `Set-Cookie` headers, only the first pair of each line is a cookie.
+fn cookie_pairs(header: &str, set_cookie: bool) -> Vec<(&str, usize, usize)> {
+    let mut pairs = Vec::new();
+    let mut line_start = 0;
+
+    for line in header.split('\n') {
+        let mut pair_start = line_start;
+        for pair in line.split(';') {
+            if let Some((name, value)) = pair.split_once('=') {
+                let value_start = pair_start + name.len() + 1;
+                let trimmed = value.trim();
+                let offset = value.len() - value.trim_start().len();
+                pairs.push((
+                    name.trim(),
+                    value_start + offset,
+                    value_start + offset + trimmed.len(),
+                ));
+            }
+            pair_start += pair.len() + 1;
+            if set_cookie {
+                break;
+            }
+        }
+        line_start += line.len() + 1;
+    }
+
+    pairs
+}
+
+/// Finds the sensitive values in the entry. A value found in several places
+/// is reported once, where it is found first.
+pub fn scan_entry(entry: &Entry) -> Vec<Finding> {
+    let mut scanner = Scanner::default();
+    let request = &entry.request;
+    let response = &entry.response;
+
+    for param in request.query_string.iter() {
+        scanner.text(&format!("query parameter {}", param.name), &param.value);
+    }
+    scanner.cookies("request cookie", &request.cookies);
+    scanner.headers("request header", &request.headers);
+    if let Some(post_data) = &request.post_data {
+        for param in post_data.params.iter().flatten() {
+            if let Some(value) = &param.value {
+                scanner.text(&format!("request param {}", param.name), value);
+            }
+        }
+        if let Some(text) = &post_data.text {
+            scanner.text("request body", text);
+        }
+    }
+
+    scanner.cookies("response cookie", &response.cookies);
+    scanner.headers("response header", &response.headers);
+    if let Some(text) = body::content_text(&response.content) {
+        scanner.text("response body", &text);
+    }
+
+    scanner.findings
+}
+
+#[derive(Default)]
+struct Scanner {
+    findings: Vec<Finding>,
+}
+
+impl Scanner {
+    fn push(&mut self, rule: Rule, location: &str, value: &str) {
+        if self
+            .findings
+            .iter()
+            .any(|finding| finding.rule == rule && finding.value == value)
+        {
+            return;
+        }
+        self.findings.push(Finding {
+            rule,
+            location: location.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    fn text(&mut self, location: &str, text: &str) {
+        for (start, end, rule) in find_ranges(text) {
+            self.push(rule, location, &text[start..end]);
+        }
+    }
+
+    fn cookies(&mut self, location: &str, cookies: &[Cookie]) {
+        for cookie in cookies {
+            let location = format!("{} {}", location, cookie.name);
+            if !cookie.value.is_empty() && is_session_cookie(&cookie.name) {
+                self.push(Rule::SessionCookie, &location, &cookie.value);
+            } else {
+                self.text(&location, &cookie.value);
+            }
+        }
+    }
+
+    fn headers(&mut self, location: &str, headers: &[Header]) {
+        for header in headers {
+            let location = format!("{} {}", location, header.name);
+            for (start, end, rule) in find_header_ranges(&header.name, &header.value) {
+                self.push(rule, &location, &header.value[start..end]);
+            }
+        }
+    }
+}
@@ -0,0 +1,477 @@
+//! Comparison of two HAR files, e.g. captures of a page before and after a release.
+//!
+//! Entries are matched by method and normalized URL. When several entries share both,
+//! they are matched in the order they were recorded.
+
+use crate::body::format_bytes;
+use crate::har::{Entry, Har, Header};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Headers whose values change with every capture, not compared unless asked for
+const VOLATILE_HEADERS: [&str; 9] = [
+    "date",
+    "age",
+    "expires",
+    "cookie",
+    "set-cookie",
+    "x-request-id",
+    "x-amzn-trace-id",
+    "cf-ray",
+    "server-timing",
+];
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Match entries by their URL without the query string
+    pub ignore_query: bool,
+    /// Also compare the headers in [`VOLATILE_HEADERS`]
+    pub all_headers: bool,
+    /// Smallest change of the total time, in milliseconds, that makes an entry changed
+    pub min_time_delta: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+impl Change {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Added => "+",
+            Self::Removed => "-",
+            Self::Changed => "~",
+            Self::Unchanged => "=",
+        }
+    }
+}
+
+/// The fields of an entry that are compared, from one of the files
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntrySide {
+    /// Position of the entry in its file, counted from 1
+    pub entry: usize,
+    /// The URL as recorded, unlike the normalized one of [`EntryDiff`]
+    pub url: String,
+    pub status: i64,
+    pub size: Option<i64>,
+    pub time: f64,
+}
+
+impl EntrySide {
+    fn new(index: usize, entry: &Entry) -> Self {
+        Self {
+            entry: index + 1,
+            url: entry.request.url.to_string(),
+            status: entry.response.status,
+            size: entry.response.content.size,
+            time: entry.time,
+        }
+    }
+
+    /// Index of the entry in `har.log.entries`
+    pub fn index(&self) -> usize {
+        self.entry - 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderKind {
+    Request,
+    Response,
+}
+
+impl fmt::Display for HeaderKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Request => write!(f, "request"),
+            Self::Response => write!(f, "response"),
+        }
+    }
+}
+
+/// A header that was added, removed or changed. Repeated headers are joined with newlines.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HeaderChange {
+    pub kind: HeaderKind,
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryDiff {
+    pub change: Change,
+    pub method: String,
+    /// The normalized URL the entries were matched by
+    pub url: String,
+    pub before: Option<EntrySide>,
+    pub after: Option<EntrySide>,
+    pub size_delta: Option<i64>,
+    pub time_delta: Option<f64>,
+    pub headers: Vec<HeaderChange>,
+}
+
+impl EntryDiff {
+    /// The URL to show: as recorded in the earlier file, or in the later one for added entries
+    pub fn display_url(&self) -> &str {
+        self.before
+            .as_ref()
+            .or(self.after.as_ref())
+            .map_or(&self.url, |side| &side.url)
+    }
+
+    pub fn status_changed(&self) -> bool {
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) => before.status != after.status,
+            _ => false,
+        }
+    }
+}
+
+/// Drops the fragment and sorts the query parameters, so that equivalent URLs are equal.
+/// The scheme and host are already lowercased and default ports dropped when parsing.
+pub fn normalize_url(url: &url::Url, ignore_query: bool) -> String {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    if ignore_query {
+        url.set_query(None);
+    } else if url.query().is_some() {
+        let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        pairs.sort();
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+    }
+    url.to_string()
+}
+
+fn match_key(entry: &Entry, options: &Options) -> (String, String) {
+    (
+        entry.request.method.to_ascii_uppercase(),
+        normalize_url(&entry.request.url, options.ignore_query),
+    )
+}
+
+/// Compares the entries of two files. Entries of the first file come first, in file order,
+/// followed by the entries only found in the second one.
+pub fn compare(before: &Har, after: &Har, options: &Options) -> Vec<EntryDiff> {
+    let mut unmatched: HashMap<(String, String), VecDeque<usize>> = HashMap::new();
+    for (i, entry) in after.log.entries.iter().enumerate() {
+        unmatched
+            .entry(match_key(entry, options))
+            .or_default()
+            .push_back(i);
+    }
+
+    let mut diffs = Vec::new();
+    let mut matched = vec![false; after.log.entries.len()];
+    for (i, entry) in before.log.entries.iter().enumerate() {
+        let (method, url) = match_key(entry, options);
+        let after_index = unmatched
+            .get_mut(&(method.clone(), url.clone()))
+            .and_then(|indices| indices.pop_front());
+        let diff = match after_index {
+            Some(j) => {
+                matched[j] = true;
+                compare_entries(method, url, (i, entry), (j, &after.log.entries[j]), options)
+            }
+            None => EntryDiff {
+                change: Change::Removed,
+                method,
+                url,
+                before: Some(EntrySide::new(i, entry)),
+                after: None,
+                size_delta: None,
+                time_delta: None,
+                headers: Vec::new(),
+            },
+        };
+        diffs.push(diff);
+    }
+
+    for (j, entry) in after.log.entries.iter().enumerate() {
+        if !matched[j] {
+            let (method, url) = match_key(entry, options);
+            diffs.push(EntryDiff {
+                change: Change::Added,
+                method,
+                url,
+                before: None,
+                after: Some(EntrySide::new(j, entry)),
+                size_delta: None,
+                time_delta: None,
+                headers: Vec::new(),
+            });
+        }
+    }
+
+    diffs
+}
+
+fn compare_entries(
+    method: String,
+    url: String,
+    (i, before): (usize, &Entry),
+    (j, after): (usize, &Entry),
+    options: &Options,
+) -> EntryDiff {
+    let mut headers = header_changes(
+        HeaderKind::Request,
+        &before.request.headers,
+        &after.request.headers,
+        options,
+    );
+    headers.extend(header_changes(
+        HeaderKind::Response,
+        &before.response.headers,
+        &after.response.headers,
+        options,
+    ));
+
+    let before = EntrySide::new(i, before);
+    let after = EntrySide::new(j, after);
+    let size_delta = match (before.size, after.size) {
+        (Some(a), Some(b)) => Some(b - a),
+        _ => None,
+    };
+    let time_delta = (before.time >= 0.0 && after.time >= 0.0).then_some(after.time - before.time);
+
+    let changed = before.status != after.status
+        || before.size != after.size
+        || time_delta.is_some_and(|delta| delta.abs() >= options.min_time_delta)
+        || !headers.is_empty();
+
+    EntryDiff {
+        change: if changed {
+            Change::Changed
+        } else {
+            Change::Unchanged
+        },
+        method,
+        url,
+        before: Some(before),
+        after: Some(after),
+        size_delta,
+        time_delta,
+        headers,
+    }
+}
+
+/// Joins the values of each header name, lowercased, in order of appearance
+fn header_values(headers: &[Header]) -> Vec<(String, String)> {
+    let mut values: Vec<(String, String)> = Vec::new();
+    for header in headers {
+        let name = header.name.to_ascii_lowercase();
+        match values.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, value)) => {
+                value.push('\n');
+                value.push_str(&header.value);
+            }
+            None => values.push((name, header.value.clone())),
+        }
+    }
+    values
+}
+
+pub fn header_changes(
+    kind: HeaderKind,
+    before: &[Header],
+    after: &[Header],
+    options: &Options,
+) -> Vec<HeaderChange> {
+    let before = header_values(before);
+    let after = header_values(after);
+    let mut names: Vec<&String> = before.iter().map(|(name, _)| name).collect();
+    for (name, _) in &after {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    let find = |values: &[(String, String)], name: &str| {
+        values
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.clone())
+    };
+    names
+        .into_iter()
+        .filter(|name| options.all_headers || !VOLATILE_HEADERS.contains(&name.as_str()))
+        .filter_map(|name| {
+            let before = find(&before, name);
+            let after = find(&after, name);
+            (before != after).then(|| HeaderChange {
+                kind,
+                name: name.clone(),
+                before,
+                after,
+            })
+        })
+        .collect()
+}
+
+/// Summary of one of the compared files
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSummary {
+    pub path: String,
+    pub entries: usize,
+}
+
+/// The differences between two files, as printed by `harview diff`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub before: FileSummary,
+    pub after: FileSummary,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub unchanged: usize,
+    /// Entries that were added, removed or changed
+    pub entries: Vec<EntryDiff>,
+}
+
+impl Report {
+    pub fn new(before: FileSummary, after: FileSummary, diffs: Vec<EntryDiff>) -> Self {
+        let count = |change| diffs.iter().filter(|diff| diff.change == change).count();
+        Self {
+            before,
+            after,
+            added: count(Change::Added),
+            removed: count(Change::Removed),
+            changed: count(Change::Changed),
+            unchanged: count(Change::Unchanged),
+            entries: diffs
+                .into_iter()
+                .filter(|diff| diff.change != Change::Unchanged)
+                .collect(),
+        }
+    }
+}
+
+/// Formats a change of size with its sign, e.g. `+2.50 kB`
+pub fn format_size_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_bytes(delta.abs()))
+}
+
+pub fn format_time_delta(delta: f64) -> String {
+    format!("{:+.0} ms", delta)
+}
+
+fn format_side(side: &EntrySide) -> String {
+    format!(
+        "{}  {}  {:.0} ms",
+        side.status,
+        side.size.map(format_bytes).unwrap_or("--- B".to_string()),
+        side.time
+    )
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "before: {} ({} entries)",
+            self.before.path, self.before.entries
+        )?;
+        writeln!(
+            f,
+            "after:  {} ({} entries)",
+            self.after.path, self.after.entries
+        )?;
+        writeln!(
+            f,
+            "{} added, {} removed, {} changed, {} unchanged",
+            self.added, self.removed, self.changed, self.unchanged
+        )?;
+
+        for diff in &self.entries {
+            writeln!(f)?;
+            write!(
+                f,
+                "{} {} {}",
+                diff.change.symbol(),
+                diff.method,
+                diff.display_url()
+            )?;
+            match (&diff.before, &diff.after) {
+                (Some(side), None) | (None, Some(side)) => {
+                    writeln!(f, "  ({})", format_side(side))?;
+                    continue;
+                }
+                _ => writeln!(f)?,
+            }
+
+            let (Some(before), Some(after)) = (&diff.before, &diff.after) else {
+                continue;
+            };
+            let mut rows: Vec<(String, String)> = Vec::new();
+            if before.status != after.status {
+                rows.push((
+                    "status".to_string(),
+                    format!("{} -> {}", before.status, after.status),
+                ));
+            }
+            if let Some(delta) = diff.size_delta.filter(|delta| *delta != 0) {
+                rows.push((
+                    "size".to_string(),
+                    format!(
+                        "{} -> {} ({})",
+                        format_bytes(before.size.unwrap_or(0)),
+                        format_bytes(after.size.unwrap_or(0)),
+                        format_size_delta(delta)
+                    ),
+                ));
+            }
+            if let Some(delta) = diff.time_delta.filter(|delta| delta.abs() >= 0.5) {
+                rows.push((
+                    "time".to_string(),
+                    format!(
+                        "{:.0} ms -> {:.0} ms ({})",
+                        before.time,
+                        after.time,
+                        format_time_delta(delta)
+                    ),
+                ));
+            }
+            for header in &diff.headers {
+                let value = |value: &Option<String>| match value {
+                    Some(value) => value.replace('\n', ", "),
+                    None => "(none)".to_string(),
+                };
+                rows.push((
+                    format!("{} {}", header.kind, header.name),
+                    format!("{} -> {}", value(&header.before), value(&header.after)),
+                ));
+            }
+
+            let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+            for (name, value) in rows {
+                writeln!(f, "    {:<width$}  {}", name, value, width = width)?;
+            }
+        }
+
+        Ok(())
+    }
+}
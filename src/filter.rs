@@ -0,0 +1,553 @@
+//! A small expression language to narrow down entries.
+//!
+//! ```text
+//! status>=400 and method:POST
+//! domain:*.example.com mime:json
+//! (size>1MB or time>500ms) and not header:authorization
+//! ```
+//!
+//! Terms written next to each other are combined with `and`.
+//! A term without a field matches like the search (`/`) does.
+
+use crate::har::Entry;
+use crate::search::Query;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (at column {})", self.message, self.position + 1)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Term(Term),
+}
+
+#[derive(Debug, Clone)]
+pub enum Term {
+    Status(Op, StatusPattern),
+    Method(Op, String),
+    Domain(Op, Glob),
+    Url(Op, Glob),
+    Mime(Op, String),
+    Type(Op, String),
+    Size(Op, f64),
+    Time(Op, f64),
+    Header(Op, String, Option<String>),
+    Body(Op, String),
+    Text(Query),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Match,
+    NotMatch,
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Op {
+    fn compare(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Match => lhs == rhs,
+            Self::NotMatch => lhs != rhs,
+            Self::Greater => lhs > rhs,
+            Self::GreaterOrEqual => lhs >= rhs,
+            Self::Less => lhs < rhs,
+            Self::LessOrEqual => lhs <= rhs,
+        }
+    }
+
+    fn is_ordering(self) -> bool {
+        !matches!(self, Self::Match | Self::NotMatch)
+    }
+
+    fn apply(self, matched: bool) -> bool {
+        match self {
+            Self::NotMatch => !matched,
+            _ => matched,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatusPattern {
+    Code(u16),
+    /// `4xx` and the like
+    Class(u16),
+}
+
+/// A case-insensitive pattern where `*` matches any sequence of characters.
+/// Without a `*`, the pattern matches any text that contains it.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_lowercase(),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        if !self.pattern.contains('*') {
+            return text.contains(&self.pattern);
+        }
+
+        let parts: Vec<&str> = self.pattern.split('*').collect();
+        let (first, rest) = parts.split_first().unwrap_or((&"", &[]));
+        let Some(mut remaining) = text.strip_prefix(first) else {
+            return false;
+        };
+
+        for (i, part) in rest.iter().enumerate() {
+            let is_last = i + 1 == rest.len();
+            if is_last {
+                return remaining.ends_with(part);
+            }
+            match remaining.find(part) {
+                Some(position) => remaining = &remaining[position + part.len()..],
+                None => return false,
+            }
+        }
+
+        remaining.is_empty()
+    }
+}
+
+impl Expr {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            end: input.len(),
+        };
+        let expr = parser.parse_or()?;
+
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(ParseError {
+                message: format!("unexpected {}", token.kind),
+                position: token.position,
+            }),
+        }
+    }
+
+    pub fn matches(&self, entry: &Entry) -> bool {
+        match self {
+            Self::And(lhs, rhs) => lhs.matches(entry) && rhs.matches(entry),
+            Self::Or(lhs, rhs) => lhs.matches(entry) || rhs.matches(entry),
+            Self::Not(expr) => !expr.matches(entry),
+            Self::Term(term) => term.matches(entry),
+        }
+    }
+}
+
+impl Term {
+    fn matches(&self, entry: &Entry) -> bool {
+        let request = &entry.request;
+        let response = &entry.response;
+
+        match self {
+            Self::Status(op, StatusPattern::Code(code)) => {
+                op.compare(response.status as f64, *code as f64)
+            }
+            Self::Status(op, StatusPattern::Class(class)) => {
+                op.apply(response.status / 100 == *class as i64)
+            }
+            Self::Method(op, method) => op.apply(request.method.eq_ignore_ascii_case(method)),
+            Self::Domain(op, glob) => op.apply(glob.is_match(request.url.host_str().unwrap_or(""))),
+            Self::Url(op, glob) => op.apply(glob.is_match(request.url.as_str())),
+            Self::Mime(op, mime) => op.apply(
+                response
+                    .content
+                    .mime_type
+                    .as_deref()
+                    .is_some_and(|mime_type| mime_type.to_lowercase().contains(mime)),
+            ),
+            Self::Type(op, resource_type) => op.apply(
+                entry
+                    .resource_type
+                    .as_deref()
+                    .is_some_and(|t| t.eq_ignore_ascii_case(resource_type)),
+            ),
+            Self::Size(op, size) => match response.content.size {
+                Some(actual) if actual >= 0 => op.compare(actual as f64, *size),
+                _ => false,
+            },
+            Self::Time(op, time) => op.compare(entry.time, *time),
+            Self::Header(op, name, value) => op.apply(
+                request
+                    .headers
+                    .iter()
+                    .chain(response.headers.iter())
+                    .any(|header| {
+                        header.name.eq_ignore_ascii_case(name)
+                            && value
+                                .as_ref()
+                                .is_none_or(|value| header.value.to_lowercase().contains(value))
+                    }),
+            ),
+            Self::Body(op, text) => {
+                let contains = |body: Option<&str>| {
+                    body.is_some_and(|body| body.to_lowercase().contains(text))
+                };
+                op.apply(
+                    contains(
+                        request
+                            .post_data
+                            .as_ref()
+                            .and_then(|post_data| post_data.text.as_deref()),
+                    ) || contains(response.content.text.as_deref()),
+                )
+            }
+            Self::Text(query) => query.matches_entry(entry),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    Word(String),
+    Field(String, Op, String),
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LeftParen => write!(f, "`(`"),
+            Self::RightParen => write!(f, "`)`"),
+            Self::And => write!(f, "`and`"),
+            Self::Or => write!(f, "`or`"),
+            Self::Not => write!(f, "`not`"),
+            Self::Word(word) => write!(f, "`{}`", word),
+            Self::Field(field, _, _) => write!(f, "`{}`", field),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (position, c) = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let kind = match c {
+            '(' => {
+                i += 1;
+                TokenKind::LeftParen
+            }
+            ')' => {
+                i += 1;
+                TokenKind::RightParen
+            }
+            '!' if chars.get(i + 1).map(|(_, c)| *c) != Some('=') => {
+                i += 1;
+                TokenKind::Not
+            }
+            '&' | '|' => {
+                if chars.get(i + 1).map(|(_, c)| *c) != Some(c) {
+                    return Err(ParseError {
+                        message: format!("expected `{}{}`", c, c),
+                        position,
+                    });
+                }
+                i += 2;
+                if c == '&' {
+                    TokenKind::And
+                } else {
+                    TokenKind::Or
+                }
+            }
+            _ => {
+                let (word, next) = read_word(&chars, i)?;
+                i = next;
+                match word.to_lowercase().as_str() {
+                    "and" => TokenKind::And,
+                    "or" => TokenKind::Or,
+                    "not" => TokenKind::Not,
+                    _ => split_field(&word),
+                }
+            }
+        };
+
+        tokens.push(Token { kind, position });
+    }
+
+    Ok(tokens)
+}
+
+/// Reads a word up to the next whitespace or parenthesis. Double quotes group characters.
+fn read_word(chars: &[(usize, char)], start: usize) -> Result<(String, usize), ParseError> {
+    let mut word = String::new();
+    let mut i = start;
+    let mut quote_start = None;
+
+    while let Some((position, c)) = chars.get(i).copied() {
+        match c {
+            '"' if quote_start.is_some() => quote_start = None,
+            '"' => quote_start = Some(position),
+            '\\' if quote_start.is_some() => {
+                if let Some((_, escaped)) = chars.get(i + 1) {
+                    word.push(*escaped);
+                    i += 1;
+                }
+            }
+            c if quote_start.is_none() && (c.is_whitespace() || c == '(' || c == ')') => break,
+            c => word.push(c),
+        }
+        i += 1;
+    }
+
+    match quote_start {
+        Some(position) => Err(ParseError {
+            message: "unterminated quote".to_string(),
+            position,
+        }),
+        None => Ok((word, i)),
+    }
+}
+
+fn split_field(word: &str) -> TokenKind {
+    const OPERATORS: [(&str, Op); 7] = [
+        (">=", Op::GreaterOrEqual),
+        ("<=", Op::LessOrEqual),
+        ("!=", Op::NotMatch),
+        (">", Op::Greater),
+        ("<", Op::Less),
+        (":", Op::Match),
+        ("=", Op::Match),
+    ];
+
+    let found = word
+        .char_indices()
+        .find_map(|(i, _)| {
+            OPERATORS
+                .iter()
+                .find(|(symbol, _)| word[i..].starts_with(symbol))
+                .map(|(symbol, op)| (i, *symbol, *op))
+        })
+        .filter(|(i, _, _)| *i > 0);
+
+    match found {
+        Some((i, symbol, op)) if is_field(&word[..i]) => TokenKind::Field(
+            word[..i].to_lowercase(),
+            op,
+            word[i + symbol.len()..].to_string(),
+        ),
+        _ => TokenKind::Word(word.to_string()),
+    }
+}
+
+const FIELDS: [&str; 12] = [
+    "status", "method", "domain", "host", "url", "mime", "type", "size", "time", "header", "body",
+    "is",
+];
+
+fn is_field(name: &str) -> bool {
+    FIELDS.contains(&name.to_lowercase().as_str())
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_and()?;
+        while self.peek().is_some_and(|token| token.kind == TokenKind::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.parse_not()?;
+        loop {
+            match self.peek().map(|token| &token.kind) {
+                Some(TokenKind::And) => {
+                    self.next();
+                }
+                Some(TokenKind::Or) | Some(TokenKind::RightParen) | None => break,
+                // Terms written next to each other are combined with `and`
+                Some(_) => {}
+            }
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ParseError> {
+        if self
+            .peek()
+            .is_some_and(|token| token.kind == TokenKind::Not)
+        {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_atom()
+    }
+
+    fn parse_atom(&mut self) -> Result<Expr, ParseError> {
+        let Some(token) = self.next() else {
+            return Err(ParseError {
+                message: "unexpected end of expression".to_string(),
+                position: self.end,
+            });
+        };
+
+        match token.kind {
+            TokenKind::LeftParen => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token {
+                        kind: TokenKind::RightParen,
+                        ..
+                    }) => Ok(expr),
+                    _ => Err(ParseError {
+                        message: "missing `)`".to_string(),
+                        position: token.position,
+                    }),
+                }
+            }
+            TokenKind::Word(word) => Ok(Expr::Term(Term::Text(Query::new(&word)))),
+            TokenKind::Field(field, op, value) => {
+                parse_term(&field, op, &value, token.position).map(Expr::Term)
+            }
+            kind => Err(ParseError {
+                message: format!("unexpected {}", kind),
+                position: token.position,
+            }),
+        }
+    }
+}
+
+fn parse_term(field: &str, op: Op, value: &str, position: usize) -> Result<Term, ParseError> {
+    let error = |message: String| ParseError { message, position };
+    let value_lower = value.to_lowercase();
+    if value.is_empty() {
+        return Err(error(format!("missing value for `{}`", field)));
+    }
+
+    match field {
+        "status" => {
+            if let Some(class) = value_lower
+                .strip_suffix("xx")
+                .and_then(|class| class.parse::<u16>().ok())
+            {
+                if op.is_ordering() {
+                    return Err(error("status classes cannot be compared".to_string()));
+                }
+                return Ok(Term::Status(op, StatusPattern::Class(class)));
+            }
+            value
+                .parse::<u16>()
+                .map(|code| Term::Status(op, StatusPattern::Code(code)))
+                .map_err(|_| error(format!("invalid status `{}`", value)))
+        }
+        "size" => parse_size(&value_lower)
+            .map(|size| Term::Size(op, size))
+            .ok_or_else(|| error(format!("invalid size `{}`", value))),
+        "time" => parse_duration(&value_lower)
+            .map(|time| Term::Time(op, time))
+            .ok_or_else(|| error(format!("invalid duration `{}`", value))),
+        _ if op.is_ordering() => Err(error(format!("`{}` cannot be compared", field))),
+        "method" => Ok(Term::Method(op, value.to_string())),
+        "domain" | "host" => Ok(Term::Domain(op, Glob::new(value))),
+        "url" => Ok(Term::Url(op, Glob::new(value))),
+        "mime" => Ok(Term::Mime(op, value_lower)),
+        "type" | "is" => Ok(Term::Type(op, value_lower)),
+        "header" => Ok(match value_lower.split_once(['=', ':']) {
+            Some((name, value)) => {
+                Term::Header(op, name.trim().to_string(), Some(value.trim().to_string()))
+            }
+            None => Term::Header(op, value_lower, None),
+        }),
+        "body" => Ok(Term::Body(op, value_lower)),
+        _ => Err(error(format!("unknown field `{}`", field))),
+    }
+}
+
+/// Parses sizes such as `512`, `10kb`, `1.5MB` or `2MiB` into bytes
+fn parse_size(value: &str) -> Option<f64> {
+    let (number, unit) = split_number(value);
+    let multiplier = match unit {
+        "" | "b" => 1.0,
+        "k" | "kb" => 1e3,
+        "m" | "mb" => 1e6,
+        "g" | "gb" => 1e9,
+        "kib" => 1024.0,
+        "mib" => 1024.0 * 1024.0,
+        "gib" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    number.parse::<f64>().ok().map(|number| number * multiplier)
+}
+
+/// Parses durations such as `500`, `500ms`, `1.5s`, `2m` or `1h` into milliseconds
+pub fn parse_duration(value: &str) -> Option<f64> {
+    let (number, unit) = split_number(value);
+    let multiplier = match unit {
+        "" | "ms" => 1.0,
+        "s" => 1e3,
+        "m" | "min" => 60e3,
+        "h" => 3600e3,
+        _ => return None,
+    };
+    number.parse::<f64>().ok().map(|number| number * multiplier)
+}
+
+fn split_number(value: &str) -> (&str, &str) {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    (&value[..end], value[end..].trim())
+}
@@ -1,5 +1,6 @@
 use crate::app;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::snippet;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 
 #[derive(Debug)]
 pub enum Command {
@@ -8,6 +9,44 @@ pub enum Command {
     TableFocusTop,
     TableFocusBottom,
     SetTabBarState(app::TabBarState),
+    HeaderSectionDelta(i32),
+    ToggleHeaderSection,
+    ToggleMainView,
+    ToggleFocus,
+    SetFocus(app::Pane),
+    SelectRow(usize),
+    StartDividerDrag,
+    DragDivider(u16, u16),
+    EndDividerDrag,
+    PreviewScrollDelta(i32),
+    PreviewScrollTop,
+    PreviewScrollBottom,
+    PageDelta(i32),
+    ToggleMark,
+    ClearMarks,
+    Redact,
+    CopySnippet(snippet::Format),
+    OpenSnippetPicker,
+    CloseSnippetPicker,
+    SnippetPickerDelta(i32),
+    CopyPickedSnippet,
+    ToggleWarningsPanel,
+    WarningsScrollDelta(i32),
+    ToggleSummary,
+    SummaryScrollDelta(i32),
+    CycleSortColumn,
+    CyclePreviewWidgetState,
+    PreviewSizeDelta(i32),
+    ReverseSortOrder,
+    OpenPrompt(app::PromptKind),
+    PromptInput(String),
+    PromptBackspace,
+    PromptClear,
+    SubmitPrompt,
+    CancelPrompt,
+    SearchNext,
+    SearchPrevious,
+    ClearSearch,
 }
 
 impl Command {
@@ -18,11 +57,65 @@ impl Command {
             Self::TableFocusBottom => app.update_index_last(),
             Self::TableFocusDelta(count) => app.update_index(*count),
             Self::SetTabBarState(state) => app.set_tabbar_state(state),
+            Self::HeaderSectionDelta(count) => app.update_header_section(*count),
+            Self::ToggleHeaderSection => app.toggle_header_section(),
+            Self::ToggleMainView => app.toggle_main_view(),
+            Self::ToggleFocus => app.toggle_focus(),
+            Self::SetFocus(pane) => app.set_focus(*pane),
+            Self::SelectRow(row) => {
+                app.set_focus(app::Pane::Table);
+                app.select_row(*row);
+            }
+            Self::StartDividerDrag => app.start_divider_drag(),
+            Self::DragDivider(column, row) => app.drag_divider(*column, *row),
+            Self::EndDividerDrag => app.end_divider_drag(),
+            Self::PreviewScrollDelta(count) => app.update_preview_scroll(*count),
+            Self::PreviewScrollTop => app.update_preview_scroll_first(),
+            Self::PreviewScrollBottom => app.update_preview_scroll_last(),
+            Self::PageDelta(count) => app.update_page(*count),
+            Self::ToggleMark => app.toggle_mark(),
+            Self::ClearMarks => app.clear_marks(),
+            Self::Redact => app.redact(),
+            Self::CopySnippet(format) => app.copy_snippet(*format),
+            Self::OpenSnippetPicker => app.open_snippet_picker(),
+            Self::CloseSnippetPicker => app.close_snippet_picker(),
+            Self::SnippetPickerDelta(delta) => app.update_snippet_picker(*delta),
+            Self::CopyPickedSnippet => app.copy_picked_snippet(),
+            Self::ToggleWarningsPanel => app.toggle_warnings_panel(),
+            Self::WarningsScrollDelta(count) => app.update_warnings_scroll(*count),
+            Self::ToggleSummary => app.toggle_summary(),
+            Self::SummaryScrollDelta(count) => app.update_summary_scroll(*count),
+            Self::CycleSortColumn => app.cycle_sort_column(),
+            Self::CyclePreviewWidgetState => app.cycle_preview_widget_state(),
+            Self::PreviewSizeDelta(delta) => app.update_preview_size(*delta),
+            Self::ReverseSortOrder => app.reverse_sort_order(),
+            Self::OpenPrompt(kind) => app.open_prompt(*kind),
+            Self::PromptInput(s) => app.prompt_push_str(s),
+            Self::PromptBackspace => app.prompt_backspace(),
+            Self::PromptClear => app.prompt_clear(),
+            Self::SubmitPrompt => app.submit_prompt(),
+            Self::CancelPrompt => app.cancel_prompt(),
+            Self::SearchNext => app.search_next(true),
+            Self::SearchPrevious => app.search_next(false),
+            Self::ClearSearch => app.clear_search(),
         }
     }
 }
 
-pub fn handle_key_events(key_event: KeyEvent) -> Option<Command> {
+pub fn handle_key_events(key_event: KeyEvent, app: &app::App) -> Option<Command> {
+    if app.prompt.is_some() {
+        return handle_prompt_key_events(key_event);
+    }
+    if app.warnings_panel.is_some() {
+        return handle_warnings_key_events(key_event);
+    }
+    if app.snippet_picker.is_some() {
+        return handle_snippet_picker_key_events(key_event);
+    }
+    if app.summary.is_some() {
+        return handle_summary_key_events(key_event);
+    }
+
     match key_event.code {
         KeyCode::Char('q') => Some(Command::Quit),
         KeyCode::Char('c') | KeyCode::Char('C') => {
@@ -32,16 +125,252 @@ pub fn handle_key_events(key_event: KeyEvent) -> Option<Command> {
                 None
             }
         }
-        KeyCode::Char('j') | KeyCode::Down => Some(Command::TableFocusDelta(1)),
-        KeyCode::Char('k') | KeyCode::Up => Some(Command::TableFocusDelta(-1)),
-        KeyCode::Char('d') => Some(Command::TableFocusDelta(3)),
-        KeyCode::Char('u') => Some(Command::TableFocusDelta(-3)),
-        KeyCode::Char('g') => Some(Command::TableFocusTop),
-        KeyCode::Char('G') => Some(Command::TableFocusBottom),
+        KeyCode::Char('s') if key_event.modifiers == KeyModifiers::CONTROL => {
+            Some(Command::OpenPrompt(app::PromptKind::Save))
+        }
+        KeyCode::Char('j') | KeyCode::Down => Some(move_command(app, 1)),
+        KeyCode::Char('k') | KeyCode::Up => Some(move_command(app, -1)),
+        KeyCode::Char('d') => Some(move_command(app, 3)),
+        KeyCode::Char('u') => Some(move_command(app, -3)),
+        KeyCode::Char('g') | KeyCode::Home => Some(match app.focused_pane() {
+            app::Pane::Table => Command::TableFocusTop,
+            app::Pane::Preview => Command::PreviewScrollTop,
+        }),
+        KeyCode::Char('G') | KeyCode::End => Some(match app.focused_pane() {
+            app::Pane::Table => Command::TableFocusBottom,
+            app::Pane::Preview => Command::PreviewScrollBottom,
+        }),
+        KeyCode::PageDown => Some(Command::PageDelta(1)),
+        KeyCode::PageUp => Some(Command::PageDelta(-1)),
+        KeyCode::Tab | KeyCode::BackTab => Some(Command::ToggleFocus),
         KeyCode::Char('1') => Some(Command::SetTabBarState(app::TabBarState::Headers)),
         KeyCode::Char('2') => Some(Command::SetTabBarState(app::TabBarState::Cookies)),
         KeyCode::Char('3') => Some(Command::SetTabBarState(app::TabBarState::Request)),
         KeyCode::Char('4') => Some(Command::SetTabBarState(app::TabBarState::Response)),
+        KeyCode::Char('5') => Some(Command::SetTabBarState(app::TabBarState::Timing)),
+        KeyCode::Char('6') => Some(Command::SetTabBarState(app::TabBarState::Security)),
+        KeyCode::Char('w') => Some(Command::ToggleMainView),
+        KeyCode::Char('W') => Some(Command::ToggleWarningsPanel),
+        KeyCode::Char('i') => Some(Command::ToggleSummary),
+        KeyCode::Char('m') => Some(Command::ToggleMark),
+        KeyCode::Char('M') => Some(Command::ClearMarks),
+        KeyCode::Char('R') => Some(Command::Redact),
+        KeyCode::Char('y') => Some(Command::CopySnippet(snippet::Format::Curl)),
+        KeyCode::Char('Y') => Some(Command::OpenSnippetPicker),
+        KeyCode::Char('s') => Some(Command::CycleSortColumn),
+        KeyCode::Char('v') => Some(Command::CyclePreviewWidgetState),
+        KeyCode::Char('+') | KeyCode::Char('=') => Some(Command::PreviewSizeDelta(5)),
+        KeyCode::Char('-') => Some(Command::PreviewSizeDelta(-5)),
+        KeyCode::Char('S') => Some(Command::ReverseSortOrder),
+        KeyCode::Char('[') => Some(Command::HeaderSectionDelta(-1)),
+        KeyCode::Char(']') => Some(Command::HeaderSectionDelta(1)),
+        KeyCode::Enter | KeyCode::Char(' ') => Some(Command::ToggleHeaderSection),
+        KeyCode::Char('/') => Some(Command::OpenPrompt(app::PromptKind::Search)),
+        KeyCode::Char('f') => Some(Command::OpenPrompt(app::PromptKind::Filter)),
+        KeyCode::Char('n') => Some(Command::SearchNext),
+        KeyCode::Char('N') => Some(Command::SearchPrevious),
+        KeyCode::Esc => Some(Command::ClearSearch),
+        _ => None,
+    }
+}
+
+/// Moves the focus in the table, or scrolls the preview when it has the focus
+fn move_command(app: &app::App, count: i32) -> Command {
+    match app.focused_pane() {
+        app::Pane::Table => Command::TableFocusDelta(count),
+        app::Pane::Preview => Command::PreviewScrollDelta(count),
+    }
+}
+
+fn handle_warnings_key_events(key_event: KeyEvent) -> Option<Command> {
+    match key_event.code {
+        KeyCode::Char('W') | KeyCode::Char('q') | KeyCode::Esc => {
+            Some(Command::ToggleWarningsPanel)
+        }
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => Some(Command::Quit),
+        KeyCode::Char('j') | KeyCode::Down => Some(Command::WarningsScrollDelta(1)),
+        KeyCode::Char('k') | KeyCode::Up => Some(Command::WarningsScrollDelta(-1)),
+        KeyCode::Char('d') | KeyCode::PageDown => Some(Command::WarningsScrollDelta(10)),
+        KeyCode::Char('u') | KeyCode::PageUp => Some(Command::WarningsScrollDelta(-10)),
+        _ => None,
+    }
+}
+
+fn handle_summary_key_events(key_event: KeyEvent) -> Option<Command> {
+    match key_event.code {
+        KeyCode::Char('i') | KeyCode::Char('q') | KeyCode::Esc => Some(Command::ToggleSummary),
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => Some(Command::Quit),
+        KeyCode::Char('j') | KeyCode::Down => Some(Command::SummaryScrollDelta(1)),
+        KeyCode::Char('k') | KeyCode::Up => Some(Command::SummaryScrollDelta(-1)),
+        KeyCode::Char('d') | KeyCode::PageDown => Some(Command::SummaryScrollDelta(10)),
+        KeyCode::Char('u') | KeyCode::PageUp => Some(Command::SummaryScrollDelta(-10)),
+        _ => None,
+    }
+}
+
+fn handle_snippet_picker_key_events(key_event: KeyEvent) -> Option<Command> {
+    match key_event.code {
+        KeyCode::Char('Y') | KeyCode::Char('q') | KeyCode::Esc => Some(Command::CloseSnippetPicker),
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => Some(Command::Quit),
+        KeyCode::Char('j') | KeyCode::Down => Some(Command::SnippetPickerDelta(1)),
+        KeyCode::Char('k') | KeyCode::Up => Some(Command::SnippetPickerDelta(-1)),
+        KeyCode::Enter | KeyCode::Char('y') => Some(Command::CopyPickedSnippet),
+        _ => None,
+    }
+}
+
+fn handle_prompt_key_events(key_event: KeyEvent) -> Option<Command> {
+    match key_event.code {
+        KeyCode::Esc => Some(Command::CancelPrompt),
+        KeyCode::Enter => Some(Command::SubmitPrompt),
+        KeyCode::Backspace => Some(Command::PromptBackspace),
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+            Some(Command::CancelPrompt)
+        }
+        KeyCode::Char('u') if key_event.modifiers == KeyModifiers::CONTROL => {
+            Some(Command::PromptClear)
+        }
+        KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(Command::PromptInput(c.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Number of lines scrolled by a turn of the mouse wheel
+const WHEEL_SCROLL_LINES: i32 = 3;
+
+pub fn handle_mouse_events(mouse_event: MouseEvent, app: &app::App) -> Option<Command> {
+    if app.prompt.is_some() {
+        return None;
+    }
+    if app.snippet_picker.is_some() {
+        return match mouse_event.kind {
+            MouseEventKind::ScrollDown => Some(Command::SnippetPickerDelta(1)),
+            MouseEventKind::ScrollUp => Some(Command::SnippetPickerDelta(-1)),
+            _ => None,
+        };
+    }
+    if app.summary.is_some() {
+        return match mouse_event.kind {
+            MouseEventKind::ScrollDown => Some(Command::SummaryScrollDelta(WHEEL_SCROLL_LINES)),
+            MouseEventKind::ScrollUp => Some(Command::SummaryScrollDelta(-WHEEL_SCROLL_LINES)),
+            _ => None,
+        };
+    }
+    if app.warnings_panel.is_some() {
+        return match mouse_event.kind {
+            MouseEventKind::ScrollDown => Some(Command::WarningsScrollDelta(WHEEL_SCROLL_LINES)),
+            MouseEventKind::ScrollUp => Some(Command::WarningsScrollDelta(-WHEEL_SCROLL_LINES)),
+            _ => None,
+        };
+    }
+
+    let (column, row) = (mouse_event.column, mouse_event.row);
+    let screen = &app.screen;
+
+    match mouse_event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if screen.is_divider(column, row) {
+                Some(Command::StartDividerDrag)
+            } else if let Some(state) = screen.tab_at(column, row) {
+                Some(Command::SetTabBarState(state))
+            } else if let Some(table_row) = screen.table_row_at(column, row) {
+                Some(Command::SelectRow(table_row))
+            } else {
+                screen.pane_at(column, row).map(Command::SetFocus)
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) => Some(Command::DragDivider(column, row)),
+        MouseEventKind::Up(MouseButton::Left) => Some(Command::EndDividerDrag),
+        MouseEventKind::ScrollDown => {
+            wheel_command(screen.pane_at(column, row)?, WHEEL_SCROLL_LINES)
+        }
+        MouseEventKind::ScrollUp => {
+            wheel_command(screen.pane_at(column, row)?, -WHEEL_SCROLL_LINES)
+        }
+        _ => None,
+    }
+}
+
+/// Scrolls the pane under the cursor, regardless of which pane has the focus
+fn wheel_command(pane: app::Pane, count: i32) -> Option<Command> {
+    Some(match pane {
+        app::Pane::Table => Command::TableFocusDelta(count),
+        app::Pane::Preview => Command::PreviewScrollDelta(count),
+    })
+}
+
+pub fn handle_paste_event(text: String, app: &app::App) -> Option<Command> {
+    if app.prompt.is_some() {
+        Some(Command::PromptInput(text))
+    } else {
+        None
+    }
+}
+
+/// Commands of `harview diff --tui`
+#[derive(Debug)]
+pub enum DiffCommand {
+    Quit,
+    FocusDelta(i32),
+    FocusTop,
+    FocusBottom,
+    PageDelta(i32),
+    DetailScrollDelta(i32),
+    ToggleUnchanged,
+}
+
+impl DiffCommand {
+    pub fn exec(&self, app: &mut app::DiffApp) {
+        match self {
+            Self::Quit => app.quit(),
+            Self::FocusDelta(count) => app.update_index(*count),
+            Self::FocusTop => app.update_index_first(),
+            Self::FocusBottom => app.update_index_last(),
+            Self::PageDelta(count) => app.update_page(*count),
+            Self::DetailScrollDelta(count) => app.update_detail_scroll(*count),
+            Self::ToggleUnchanged => app.toggle_unchanged(),
+        }
+    }
+}
+
+pub fn handle_diff_key_events(key_event: KeyEvent) -> Option<DiffCommand> {
+    match key_event.code {
+        KeyCode::Char('q') | KeyCode::Esc => Some(DiffCommand::Quit),
+        KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL => {
+            Some(DiffCommand::Quit)
+        }
+        KeyCode::Char('j') | KeyCode::Down => Some(DiffCommand::FocusDelta(1)),
+        KeyCode::Char('k') | KeyCode::Up => Some(DiffCommand::FocusDelta(-1)),
+        KeyCode::Char('d') => Some(DiffCommand::FocusDelta(3)),
+        KeyCode::Char('u') => Some(DiffCommand::FocusDelta(-3)),
+        KeyCode::Char('g') | KeyCode::Home => Some(DiffCommand::FocusTop),
+        KeyCode::Char('G') | KeyCode::End => Some(DiffCommand::FocusBottom),
+        KeyCode::PageDown => Some(DiffCommand::PageDelta(1)),
+        KeyCode::PageUp => Some(DiffCommand::PageDelta(-1)),
+        KeyCode::Char('J') => Some(DiffCommand::DetailScrollDelta(1)),
+        KeyCode::Char('K') => Some(DiffCommand::DetailScrollDelta(-1)),
+        KeyCode::Char('a') => Some(DiffCommand::ToggleUnchanged),
+        _ => None,
+    }
+}
+
+/// The wheel scrolls the side-by-side panes under the pointer, or moves the focus in the table
+pub fn handle_diff_mouse_events(
+    mouse_event: MouseEvent,
+    app: &app::DiffApp,
+) -> Option<DiffCommand> {
+    let over_detail = app.is_over_detail(mouse_event.column, mouse_event.row);
+    match mouse_event.kind {
+        MouseEventKind::ScrollDown if over_detail => {
+            Some(DiffCommand::DetailScrollDelta(WHEEL_SCROLL_LINES))
+        }
+        MouseEventKind::ScrollUp if over_detail => {
+            Some(DiffCommand::DetailScrollDelta(-WHEEL_SCROLL_LINES))
+        }
+        MouseEventKind::ScrollDown => Some(DiffCommand::FocusDelta(1)),
+        MouseEventKind::ScrollUp => Some(DiffCommand::FocusDelta(-1)),
         _ => None,
     }
 }
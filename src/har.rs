@@ -1,15 +1,198 @@
-use serde::{Deserialize, Deserializer};
-use std::{fs, io::BufReader, path::Path};
+use crate::lenient::{self, Warning};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+};
 use url::Url;
 
 impl Har {
+    /// Loads a HAR file, or reads it from the standard input if the path is `-`.
+    /// Compressed files are decompressed first.
     pub async fn from_file(path: &Path) -> anyhow::Result<Self> {
-        let file = fs::File::open(path)?;
-        let reader = BufReader::new(file);
-        let har = serde_json::from_reader(reader)?;
+        let bytes = read_input(path)?;
+        let deserializer = &mut serde_json::Deserializer::from_slice(&bytes);
+        let har = serde_path_to_error::deserialize(deserializer).map_err(|err| {
+            anyhow::anyhow!("{}: {}", lenient::json_path("", err.path()), err.inner())
+        })?;
 
         Ok(har)
     }
+
+    /// Loads a HAR file like `from_file`, but skips or repairs the parts that do not match
+    /// the model instead of failing
+    pub async fn from_file_lenient(path: &Path) -> anyhow::Result<(Self, Vec<Warning>)> {
+        let bytes = read_input(path)?;
+        let value: serde_json::Value = serde_json::from_slice(&bytes)?;
+
+        lenient::deserialize_har(value)
+    }
+
+    /// Writes the HAR as pretty-printed JSON, or to the standard output if the path is `-`.
+    /// The output is compressed when the path ends with `.gz`, `.zst` or `.br`.
+    /// Fields are written in the order of the spec, followed by the custom fields of each object
+    /// in the order they were read.
+    pub fn to_file(&self, path: &Path) -> anyhow::Result<()> {
+        self.write(path, false)
+    }
+
+    /// Writes the HAR like `to_file`, but fails with [`io::ErrorKind::AlreadyExists`]
+    /// instead of replacing an existing file
+    pub fn to_new_file(&self, path: &Path) -> anyhow::Result<()> {
+        self.write(path, true)
+    }
+
+    fn write(&self, path: &Path, create_new: bool) -> anyhow::Result<()> {
+        let mut json = serde_json::to_vec_pretty(self)?;
+        json.push(b'\n');
+
+        if path == Path::new("-") {
+            io::stdout().write_all(&json)?;
+        } else {
+            let compression = Compression::detect(&[], Some(path));
+            let bytes = compression.compress(json)?;
+            let mut options = fs::OpenOptions::new();
+            if create_new {
+                options.write(true).create_new(true);
+            } else {
+                options.write(true).create(true).truncate(true);
+            }
+            options.open(path)?.write_all(&bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns a copy that only contains the given entries, in the order of the original file.
+    /// Pages without any of the entries are left out.
+    pub fn with_entries(&self, indices: &[usize]) -> Self {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let entries: Vec<Entry> = indices
+            .iter()
+            .filter_map(|i| self.log.entries.get(*i).cloned())
+            .collect();
+        let pages = self.log.pages.as_ref().map(|pages| {
+            pages
+                .iter()
+                .filter(|page| {
+                    entries
+                        .iter()
+                        .any(|entry| entry.pageref.as_ref() == Some(&page.id))
+                })
+                .cloned()
+                .collect()
+        });
+
+        Self {
+            log: Log {
+                version: self.log.version.clone(),
+                creator: self.log.creator.clone(),
+                browser: self.log.browser.clone(),
+                pages,
+                entries,
+                comment: self.log.comment.clone(),
+                custom: self.log.custom.clone(),
+            },
+        }
+    }
+}
+
+fn read_input(path: &Path) -> anyhow::Result<Vec<u8>> {
+    let (bytes, compression) = if path == Path::new("-") {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        let compression = Compression::detect(&bytes, None);
+        (bytes, compression)
+    } else {
+        let bytes = fs::read(path)?;
+        let compression = Compression::detect(&bytes, Some(path));
+        (bytes, compression)
+    };
+
+    compression.decompress(bytes)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+    Brotli,
+}
+
+impl Compression {
+    const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+    const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+    /// Detects the compression from the magic bytes, then from the file extension.
+    /// Brotli streams have no magic bytes, so they are only recognized by the extension.
+    fn detect(bytes: &[u8], path: Option<&Path>) -> Self {
+        if bytes.starts_with(&Self::GZIP_MAGIC) {
+            return Self::Gzip;
+        }
+        if bytes.starts_with(&Self::ZSTD_MAGIC) {
+            return Self::Zstd;
+        }
+
+        let extension = path
+            .and_then(|path| path.extension())
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("gz") => Self::Gzip,
+            Some("zst") => Self::Zstd,
+            Some("br") => Self::Brotli,
+            _ => Self::None,
+        }
+    }
+
+    fn decompress(self, bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let mut decompressed = Vec::new();
+        match self {
+            Self::None => return Ok(bytes),
+            Self::Gzip => {
+                flate2::read::MultiGzDecoder::new(bytes.as_slice())
+                    .read_to_end(&mut decompressed)?;
+            }
+            Self::Zstd => {
+                zstd::stream::read::Decoder::new(bytes.as_slice())?
+                    .read_to_end(&mut decompressed)?;
+            }
+            Self::Brotli => {
+                brotli::Decompressor::new(bytes.as_slice(), 4096).read_to_end(&mut decompressed)?;
+            }
+        }
+
+        Ok(decompressed)
+    }
+
+    fn compress(self, bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let mut compressed = Vec::new();
+        match self {
+            Self::None => return Ok(bytes),
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(&mut compressed, flate2::Compression::default());
+                encoder.write_all(&bytes)?;
+                encoder.finish()?;
+            }
+            Self::Zstd => {
+                compressed = zstd::stream::encode_all(bytes.as_slice(), 0)?;
+            }
+            Self::Brotli => {
+                let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 9, 22);
+                encoder.write_all(&bytes)?;
+                // Finishes the stream
+                encoder.into_inner();
+            }
+        }
+
+        Ok(compressed)
+    }
 }
 
 fn deserialize_url<'de, D>(deserializer: D) -> Result<Url, D::Error>
@@ -20,163 +203,389 @@ where
     Url::parse(&s).map_err(serde::de::Error::custom)
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// Fields of an object that are not part of the model, kept so they can be written back.
+/// These are usually the custom fields the spec prefixes with `_`, but `#[serde(flatten)]`
+/// collects every unknown key.
+pub type CustomFields = serde_json::Map<String, serde_json::Value>;
+
+fn serialize_url<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(url.as_str())
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Har {
     pub log: Log,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Log {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub creator: Option<Creator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub browser: Option<Browser>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pages: Option<Vec<Page>>,
     pub entries: Vec<Entry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Creator {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Browser {
     pub name: String,
     pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Page {
-    pub id: String,
-    pub page_timings: PageTimings,
     pub started_date_time: String,
+    pub id: String,
     pub title: String,
+    pub page_timings: PageTimings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageTimings {
-    pub on_content_load: i64,
-    pub on_load: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_content_load: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_load: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Entry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pageref: Option<String>,
     pub started_date_time: String,
+    pub time: f64,
     pub request: Request,
     pub response: Response,
     pub cache: Cache,
     pub timings: Timings,
-    pub time: f64,
-    #[serde(rename = "_securityState")]
-    pub security_state: Option<String>,
-    pub pageref: Option<String>,
-    #[serde(rename = "serverIPAddress")]
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
     pub server_ipaddress: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connection: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(rename = "_securityState", skip_serializing_if = "Option::is_none")]
+    pub security_state: Option<String>,
+    #[serde(rename = "_securityDetails", skip_serializing_if = "Option::is_none")]
+    pub security_details: Option<SecurityDetails>,
+    #[serde(rename = "_securityInfo", skip_serializing_if = "Option::is_none")]
+    pub security_info: Option<SecurityInfo>,
+    #[serde(rename = "_resourceType", skip_serializing_if = "Option::is_none")]
+    pub resource_type: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// TLS details exported by Chromium based browsers
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_exchange: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_exchange_group: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cipher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub san_list: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// Seconds since the UNIX epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<f64>,
+    /// Seconds since the UNIX epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<f64>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
+
+/// TLS details exported by Firefox
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cipher_suite: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kea_group_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_scheme_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cert: Option<Certificate>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Certificate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<CertificateName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<CertificateName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity: Option<CertificateValidity>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateName {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub common_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organizational_unit: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateValidity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Request {
-    pub body_size: i64,
     pub method: String,
-    #[serde(deserialize_with = "deserialize_url")]
+    #[serde(deserialize_with = "deserialize_url", serialize_with = "serialize_url")]
     pub url: url::Url,
     pub http_version: String,
-    pub headers: Vec<Header>,
     pub cookies: Vec<Cookie>,
+    pub headers: Vec<Header>,
     pub query_string: Vec<QueryString>,
-    pub headers_size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub post_data: Option<PostData>,
+    pub headers_size: i64,
+    pub body_size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     pub name: String,
     pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Cookie {
     pub name: String,
     pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secure: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub same_site: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryString {
     pub name: String,
     pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PostData {
     pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub params: Option<Vec<Param>>,
-    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Param {
     pub name: String,
-    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub status: i64,
     pub status_text: String,
     pub http_version: String,
-    pub headers: Vec<Header>,
     pub cookies: Vec<Cookie>,
+    pub headers: Vec<Header>,
     pub content: Content,
     #[serde(rename = "redirectURL")]
     pub redirect_url: String,
     pub headers_size: i64,
     pub body_size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Header2 {
-    pub name: String,
-    pub value: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Content {
-    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Cache {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_request: Option<CacheEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_request: Option<CacheEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Cache {}
+pub struct CacheEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+    pub last_access: String,
+    #[serde(rename = "eTag")]
+    pub e_tag: String,
+    pub hit_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
+}
 
-#[derive(Debug, Clone, PartialEq, Deserialize)]
+/// Fields are declared in the order of the spec, which is the order they are written in
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Timings {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub blocked: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dns: Option<f64>,
-    pub ssl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub connect: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub send: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub wait: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub receive: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssl: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(flatten)]
+    pub custom: CustomFields,
 }
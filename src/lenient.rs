@@ -0,0 +1,254 @@
+//! Lenient deserialization of HAR files.
+//!
+//! Each page and entry is deserialized on its own. When a value does not match the model,
+//! the offending field is dropped, or filled with a placeholder if it is required, and the
+//! deserialization is retried. Parts that cannot be repaired are skipped.
+//! Every repair is recorded as a [`Warning`] with the JSON path of the field.
+
+use crate::har::{Entry, Har, Log, Page};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use serde_path_to_error::{Path, Segment};
+use std::fmt;
+
+/// Upper bound of repairs on a single page or entry, so a hopeless one is skipped quickly
+const MAX_REPAIRS: usize = 32;
+
+/// Values tried in order for a missing required field, until one is accepted
+const PLACEHOLDERS: [fn() -> Value; 6] = [
+    || Value::String(String::new()),
+    || Value::String("about:blank".to_string()),
+    || Value::from(-1),
+    || Value::from(-1.0),
+    || Value::Array(Vec::new()),
+    || Value::Object(Map::new()),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warning {
+    /// JSON path of the field in the original file, e.g. `log.entries[3].response.status`
+    pub path: String,
+    pub message: String,
+    /// Index of the entry the warning belongs to, in the loaded entries.
+    /// `None` for warnings outside entries and for skipped entries.
+    pub entry: Option<usize>,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+pub fn deserialize_har(mut value: Value) -> anyhow::Result<(Har, Vec<Warning>)> {
+    let mut warnings = Vec::new();
+
+    let log = value
+        .get_mut("log")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| anyhow::anyhow!("log: missing `log` object"))?;
+    let entries = log.insert("entries".to_string(), Value::Array(Vec::new()));
+    let pages = log.remove("pages");
+
+    let mut log: Log = deserialize(value["log"].take(), "log", None, &mut warnings)
+        .ok_or_else(|| anyhow::anyhow!("log: the log could not be read"))?;
+
+    log.pages = match pages {
+        None | Some(Value::Null) => None,
+        Some(Value::Array(pages)) => Some(
+            pages
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, page)| {
+                    deserialize::<Page>(page, &format!("log.pages[{}]", i), None, &mut warnings)
+                })
+                .collect(),
+        ),
+        Some(_) => {
+            warnings.push(Warning {
+                path: "log.pages".to_string(),
+                message: "expected an array, dropped the pages".to_string(),
+                entry: None,
+            });
+            None
+        }
+    };
+
+    match entries {
+        Some(Value::Array(entries)) => {
+            for (i, entry) in entries.into_iter().enumerate() {
+                let index = log.entries.len();
+                let path = format!("log.entries[{}]", i);
+                if let Some(entry) = deserialize::<Entry>(entry, &path, Some(index), &mut warnings)
+                {
+                    log.entries.push(entry);
+                }
+            }
+        }
+        _ => warnings.push(Warning {
+            path: "log.entries".to_string(),
+            message: "expected an array, no entries were loaded".to_string(),
+            entry: None,
+        }),
+    }
+
+    Ok((Har { log }, warnings))
+}
+
+/// Deserializes the value, repairing it until it matches `T`.
+/// Returns `None` and records a warning if it cannot be repaired.
+fn deserialize<T: DeserializeOwned>(
+    mut value: Value,
+    prefix: &str,
+    entry: Option<usize>,
+    warnings: &mut Vec<Warning>,
+) -> Option<T> {
+    let mut repairs = Vec::new();
+
+    for _ in 0..MAX_REPAIRS {
+        let err = match try_deserialize::<T>(&value) {
+            Ok(parsed) => {
+                warnings.extend(repairs.into_iter().map(|(path, message)| Warning {
+                    path,
+                    message,
+                    entry,
+                }));
+                return Some(parsed);
+            }
+            Err(err) => err,
+        };
+
+        let mut path = json_path(prefix, &err.0);
+        if let Some(field) = missing_field(&err.1) {
+            path = format!("{}.{}", path, field);
+        }
+        let Some(action) = repair::<T>(&mut value, &err) else {
+            break;
+        };
+        match repairs.last_mut() {
+            // An invalid required field is dropped first and then filled; report it once
+            Some((last_path, last_message))
+                if *last_path == path && action.starts_with("filled") =>
+            {
+                *last_message = last_message
+                    .replace("dropped the value", &action.replace("filled", "replaced"));
+            }
+            _ => repairs.push((path, format!("{}, {}", err.1, action))),
+        }
+    }
+
+    // The repairs of a skipped value are not interesting, only the reason it was skipped
+    let reason = match try_deserialize::<T>(&value) {
+        Err((path, message)) => format!("{} at {}", message, json_path(prefix, &path)),
+        Ok(_) => "too many invalid fields".to_string(),
+    };
+    warnings.push(Warning {
+        path: prefix.to_string(),
+        message: format!("skipped: {}", reason),
+        entry: None,
+    });
+
+    None
+}
+
+type Error = (Path, String);
+
+fn try_deserialize<T: DeserializeOwned>(value: &Value) -> Result<T, Error> {
+    serde_path_to_error::deserialize(value).map_err(|err| {
+        let message = err.inner().to_string();
+        (err.path().clone(), message)
+    })
+}
+
+/// Changes the value so the error does not occur again.
+/// Returns a description of the change, or `None` if nothing could be done.
+fn repair<T: DeserializeOwned>(value: &mut Value, (path, message): &Error) -> Option<String> {
+    let segments: Vec<&Segment> = path.iter().collect();
+
+    if let Some(field) = missing_field(message) {
+        // The path points at the object the field is missing from
+        let field_path = match json_path("", path).as_str() {
+            "." => field.to_string(),
+            object_path => format!("{}.{}", object_path, field),
+        };
+        for placeholder in PLACEHOLDERS {
+            let placeholder = placeholder();
+            let object = value_at(value, &segments)?.as_object_mut()?;
+            object.insert(field.to_string(), placeholder.clone());
+
+            // The placeholder fits unless the field itself is still rejected
+            let accepted = match try_deserialize::<T>(value) {
+                Ok(_) => true,
+                Err((next_path, _)) => {
+                    let next_path = json_path("", &next_path);
+                    next_path != field_path
+                        && !next_path.starts_with(&format!("{}.", field_path))
+                        && !next_path.starts_with(&format!("{}[", field_path))
+                }
+            };
+            if accepted {
+                return Some(format!("filled with {}", placeholder));
+            }
+        }
+
+        let object = value_at(value, &segments)?.as_object_mut()?;
+        object.remove(field);
+        return None;
+    }
+
+    // Drop the invalid value; a required field comes back as a missing field next time
+    let (last, parent) = segments.split_last()?;
+    match (value_at(value, parent)?, last) {
+        (Value::Object(object), Segment::Map { key }) => {
+            object.remove(key)?;
+        }
+        (Value::Array(array), Segment::Seq { index }) if *index < array.len() => {
+            array.remove(*index);
+        }
+        _ => return None,
+    }
+
+    Some("dropped the value".to_string())
+}
+
+fn missing_field(message: &str) -> Option<&str> {
+    message.strip_prefix("missing field `")?.split('`').next()
+}
+
+fn value_at<'a>(value: &'a mut Value, segments: &[&Segment]) -> Option<&'a mut Value> {
+    segments
+        .iter()
+        .try_fold(value, |value, segment| match segment {
+            Segment::Map { key } => value.get_mut(key.as_str()),
+            Segment::Seq { index } => value.get_mut(*index),
+            _ => None,
+        })
+}
+
+/// Formats a path like `log.entries[3].response.status`
+pub fn json_path(prefix: &str, path: &Path) -> String {
+    let mut json_path = prefix.to_string();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => json_path.push_str(&format!("[{}]", index)),
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                if !json_path.is_empty() {
+                    json_path.push('.');
+                }
+                json_path.push_str(key);
+            }
+            Segment::Unknown => {
+                if !json_path.is_empty() {
+                    json_path.push('.');
+                }
+                json_path.push('?');
+            }
+        }
+    }
+
+    if json_path.is_empty() {
+        ".".to_string()
+    } else {
+        json_path
+    }
+}
@@ -0,0 +1,206 @@
+//! Listing of entries as plain text, for shell scripts and logs without a terminal.
+
+use crate::body::format_bytes;
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Columns aligned like the table of the TUI
+    Table,
+    Csv,
+    Tsv,
+    /// One JSON object per line
+    Jsonl,
+}
+
+/// The columns of the entry table with their raw values
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Record {
+    /// Position of the entry in the file, counted from 1
+    pub entry: usize,
+    /// `0` when the request got no response
+    pub status: u16,
+    pub method: String,
+    pub domain: String,
+    pub file_name: String,
+    pub content_type: String,
+    /// Response body size in bytes
+    pub size: Option<i64>,
+    /// Total time in milliseconds
+    pub time: Option<f64>,
+    pub started: Option<String>,
+}
+
+const COLUMNS: [&str; 9] = [
+    "entry",
+    "status",
+    "method",
+    "domain",
+    "fileName",
+    "contentType",
+    "size",
+    "time",
+    "started",
+];
+
+const TABLE_COLUMNS: [&str; 9] = [
+    "#",
+    "Status",
+    "Method",
+    "Domain",
+    "FileName",
+    "ContentType",
+    "Size",
+    "Time",
+    "Started",
+];
+
+/// Columns aligned to the right in the table: the entry number, size and time
+const TABLE_RIGHT_ALIGNED: [usize; 3] = [0, 6, 7];
+
+impl Record {
+    fn to_fields(&self) -> [String; 9] {
+        [
+            self.entry.to_string(),
+            self.status.to_string(),
+            self.method.clone(),
+            self.domain.clone(),
+            self.file_name.clone(),
+            self.content_type.clone(),
+            self.size.map(|size| size.to_string()).unwrap_or_default(),
+            self.time.map(|time| time.to_string()).unwrap_or_default(),
+            self.started.clone().unwrap_or_default(),
+        ]
+    }
+
+    /// Fields formatted for people, like the cells of the TUI table
+    fn to_table_fields(&self) -> [String; 9] {
+        let status = match self.status {
+            0 => "---".to_string(),
+            status => status.to_string(),
+        };
+        let content_type = match self.content_type.parse::<mime::Mime>() {
+            Ok(m) => m.subtype().to_string(),
+            Err(_) => self.content_type.clone(),
+        };
+        let size = match self.size {
+            Some(size) => format_bytes(size),
+            None => "--- B".to_string(),
+        };
+        let time = match self.time {
+            Some(time) => format!("{:.0} ms", time),
+            None => "--- ms".to_string(),
+        };
+
+        [
+            self.entry.to_string(),
+            status,
+            self.method.clone(),
+            self.domain.clone(),
+            self.file_name.clone(),
+            content_type,
+            size,
+            time,
+            self.started.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+pub fn write(
+    out: &mut impl Write,
+    records: &[Record],
+    format: OutputFormat,
+    header: bool,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Table => write_table(out, records, header)?,
+        OutputFormat::Csv => write_delimited(out, records, b',', header)?,
+        OutputFormat::Tsv => write_delimited(out, records, b'\t', header)?,
+        OutputFormat::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut *out, record)?;
+                writeln!(out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_table(out: &mut impl Write, records: &[Record], header: bool) -> io::Result<()> {
+    let rows: Vec<Vec<String>> = header
+        .then(|| TABLE_COLUMNS.map(String::from).to_vec())
+        .into_iter()
+        .chain(
+            records
+                .iter()
+                .map(|record| record.to_table_fields().to_vec()),
+        )
+        .collect();
+    for line in align_columns(&rows, &TABLE_RIGHT_ALIGNED) {
+        writeln!(out, "{}", line)?;
+    }
+
+    Ok(())
+}
+
+/// Pads the cells of each row to the width of their column, separated by two spaces
+pub fn align_columns(rows: &[Vec<String>], right_aligned: &[usize]) -> Vec<String> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(i) {
+                Some(max) => *max = (*max).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if right_aligned.contains(&i) {
+                        format!("{:>width$}", cell, width = widths[i])
+                    } else {
+                        format!("{:<width$}", cell, width = widths[i])
+                    }
+                })
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect()
+}
+
+fn write_delimited(
+    out: &mut impl Write,
+    records: &[Record],
+    delimiter: u8,
+    header: bool,
+) -> io::Result<()> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(out);
+    if header {
+        writer.write_record(COLUMNS).map_err(csv_error)?;
+    }
+    for record in records {
+        writer.write_record(record.to_fields()).map_err(csv_error)?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Keeps the kind of I/O errors, so a closed pipe can be told apart from other errors
+fn csv_error(err: csv::Error) -> io::Error {
+    match err.into_kind() {
+        csv::ErrorKind::Io(err) => err,
+        kind => io::Error::other(format!("{:?}", kind)),
+    }
+}
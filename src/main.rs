@@ -1,16 +1,27 @@
 mod app;
+mod body;
+mod detect;
+mod diff;
 mod event;
+mod filter;
 mod handler;
 mod har;
+mod lenient;
+mod list;
+mod merge;
+mod sanitize;
+mod search;
+mod snippet;
+mod split;
+mod stats;
 mod tui;
 mod ui;
 use anyhow::Context;
-use clap;
 use clap::Parser;
 use har::Har;
 use ratatui::prelude::*;
-use std::path::PathBuf;
-use tokio;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 #[derive(clap::Parser, Debug)]
 #[command(
@@ -22,23 +33,610 @@ By using the path of the HTTP Archive file exported from the developer tools of
 as the first argument, 
 you can read the file and view the HTTP communication log without opening the browser. "
 )]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(help = "Path of the HTTP Archive file to be loaded")]
-    path: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(
+        required = true,
+        help = "Path of the HTTP Archive file to be loaded (`-` to read from stdin)",
+        long_help = "Path of the HTTP Archive file to be loaded.
+Use `-` to read from the standard input.
+Files compressed with gzip, zstd or brotli (`.har.gz`, `.har.zst`, `.har.br`) are decompressed automatically."
+    )]
+    path: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Skip or repair entries that do not follow the HAR format instead of failing"
+    )]
+    lenient: bool,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Also redact the names in this rules file with `R` (see `harview sanitize --rules`)"
+    )]
+    rules: Option<PathBuf>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Mask secrets in a HAR file before sharing it
+    #[command(long_about = "Mask secrets in a HAR file before sharing it.
+Credentials in well-known headers, query parameters and body keys, and all cookie values,
+are masked by default. Add names with the options below or with a rules file.
+Names are matched case-insensitively, and may contain `*` as a wildcard.
+Free-form text such as HTML bodies and comments is not masked; review the output before sharing.")]
+    Sanitize(SanitizeArgs),
+    /// Print commands that reproduce the requests of the entries
+    Export(ExportArgs),
+    /// Print the entries as a table, CSV, TSV or JSON Lines
+    List(ListArgs),
+    /// Print a summary of the entries: sizes, breakdowns, the slowest and largest requests
+    Stats(StatsArgs),
+    /// Compare two HAR files, e.g. captures of a page before and after a release
+    #[command(
+        long_about = "Compare two HAR files, e.g. captures of a page before and after a release.
+Entries are matched by method and URL, ignoring the fragment and the order of query parameters.
+Added and removed requests are listed, along with status, size, timing and header differences
+of the matched ones. Headers that change with every capture, such as `Date` and cookies,
+are not compared unless `--all-headers` is given."
+    )]
+    Diff(DiffArgs),
+    /// Combine several HAR files into one, e.g. captures of one flow across browser sessions
+    #[command(
+        long_about = "Combine several HAR files into one, e.g. captures of one flow across browser sessions.
+Entries are ordered by their start time. Pages are renumbered so their ids stay unique,
+and the `pageref` of every entry follows its page. Pages with the same start time and title,
+and entries that are equal, are only kept once."
+    )]
+    Merge(MergeArgs),
+    /// Break a HAR file up into one file per page, domain or time window
+    Split(SplitArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct SanitizeArgs {
+    #[arg(help = "Path of the HTTP Archive file to be sanitized (`-` to read from stdin)")]
+    input: PathBuf,
+
+    #[arg(
+        short,
+        long,
+        default_value = "-",
+        help = "Path of the sanitized file (`-` for stdout); compressed by its extension"
+    )]
+    output: PathBuf,
+
+    #[arg(
+        long,
+        help = "Replace values with a truncated HMAC-SHA256 hash under a random key instead of `[REDACTED]`, so equal values can still be correlated"
+    )]
+    hash: bool,
+
+    #[arg(
+        long,
+        value_name = "KEY",
+        help = "Hash with this key instead of a random one, so values can be correlated across files (implies --hash)"
+    )]
+    hash_key: Option<String>,
+
+    #[arg(long, value_name = "NAME", help = "Also mask this header")]
+    header: Vec<String>,
+
+    #[arg(long, value_name = "NAME", help = "Also mask this cookie")]
+    cookie: Vec<String>,
+
+    #[arg(long, value_name = "NAME", help = "Also mask this query parameter")]
+    query: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Also mask this key in JSON and form bodies"
+    )]
+    body_key: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Read more names from a JSON file with `headers`, `cookies`, `queryParams` and `bodyKeys` arrays"
+    )]
+    rules: Option<PathBuf>,
+
+    #[arg(long, help = "Only use the given names, not the built-in ones")]
+    no_builtin: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct ExportArgs {
+    #[arg(help = "Path of the HTTP Archive file to be loaded (`-` to read from stdin)")]
+    input: PathBuf,
+
+    #[arg(short, long, value_enum, default_value_t = snippet::Format::Curl)]
+    format: snippet::Format,
+
+    #[arg(
+        short,
+        long,
+        value_name = "N",
+        help = "Only export the N-th entry, counted from 1 in file order; can be repeated"
+    )]
+    entry: Vec<usize>,
+
+    #[arg(
+        long,
+        value_name = "EXPR",
+        help = "Only export the entries matching the filter expression"
+    )]
+    filter: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ListArgs {
+    #[arg(help = "Path of the HTTP Archive file to be loaded (`-` to read from stdin)")]
+    input: PathBuf,
+
+    #[arg(short, long, value_enum, default_value_t = list::OutputFormat::Table)]
+    format: list::OutputFormat,
+
+    #[arg(
+        long,
+        value_name = "EXPR",
+        help = "Only list the entries matching the filter expression"
+    )]
+    filter: Option<String>,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_name = "COLUMN",
+        help = "Sort the entries by a column instead of the file order"
+    )]
+    sort: Option<app::SortColumn>,
+
+    #[arg(short, long, help = "Reverse the sort order")]
+    reverse: bool,
+
+    #[arg(
+        long,
+        help = "Do not print the header line of table, CSV and TSV output"
+    )]
+    no_header: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct StatsArgs {
+    #[arg(help = "Path of the HTTP Archive file to be loaded (`-` to read from stdin)")]
+    input: PathBuf,
+
+    #[arg(short, long, value_enum, default_value_t = stats::OutputFormat::Text)]
+    format: stats::OutputFormat,
+
+    #[arg(
+        long,
+        value_name = "EXPR",
+        help = "Only summarize the entries matching the filter expression"
+    )]
+    filter: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 10,
+        help = "Number of the slowest and largest requests to list"
+    )]
+    top: usize,
+}
+
+#[derive(clap::Args, Debug)]
+struct DiffArgs {
+    #[arg(help = "Path of the earlier HTTP Archive file")]
+    before: PathBuf,
+
+    #[arg(help = "Path of the later HTTP Archive file")]
+    after: PathBuf,
+
+    #[arg(short, long, value_enum, default_value_t = diff::OutputFormat::Text)]
+    format: diff::OutputFormat,
+
+    #[arg(
+        long,
+        help = "Show the captures side by side in the TUI instead of printing"
+    )]
+    tui: bool,
+
+    #[arg(long, help = "Match entries by their URL without the query string")]
+    ignore_query: bool,
+
+    #[arg(long, help = "Also compare headers that change with every capture")]
+    all_headers: bool,
+
+    #[arg(
+        long,
+        value_name = "MS",
+        default_value_t = 100.0,
+        help = "Smallest change of the total time that counts as a difference"
+    )]
+    min_time_delta: f64,
+}
+
+#[derive(clap::Args, Debug)]
+struct MergeArgs {
+    #[arg(
+        required = true,
+        num_args = 2..,
+        help = "Paths of the HTTP Archive files to be merged"
+    )]
+    inputs: Vec<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        default_value = "-",
+        help = "Path of the merged file (`-` for stdout); compressed by its extension"
+    )]
+    output: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+struct SplitArgs {
+    #[arg(help = "Path of the HTTP Archive file to be split (`-` to read from stdin)")]
+    input: PathBuf,
+
+    #[arg(short, long, value_enum, default_value_t = split::SplitBy::Page)]
+    by: split::SplitBy,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "1m",
+        value_parser = parse_window,
+        help = "Length of the time windows with `--by time`, e.g. `500ms`, `30s`, `5m` or `1h`"
+    )]
+    window: f64,
+
+    #[arg(
+        short,
+        long,
+        value_name = "DIR",
+        default_value = ".",
+        help = "Directory to write the files to, named `<input>.<part>.har`"
+    )]
+    output: PathBuf,
+
+    #[arg(
+        long,
+        help = "Replace files left by an earlier split instead of failing"
+    )]
+    force: bool,
+}
+
+fn parse_window(value: &str) -> Result<f64, String> {
+    match filter::parse_duration(&value.to_lowercase()) {
+        Some(window) if window > 0.0 => Ok(window),
+        _ => Err(format!("invalid duration `{}`", value)),
+    }
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let har = Har::from_file(args.path.as_path())
-        .await
-        .context("failed to parse HAR file")?;
-    let mut app = app::App::init(har);
-    run(&mut app).await?;
+    match args.command {
+        Some(Command::Sanitize(command)) => sanitize(command, args.lenient).await,
+        Some(Command::Export(command)) => export(command, args.lenient).await,
+        Some(Command::List(command)) => list(command, args.lenient).await,
+        Some(Command::Stats(command)) => stats(command, args.lenient).await,
+        Some(Command::Diff(command)) => diff(command, args.lenient).await,
+        Some(Command::Merge(command)) => merge(command, args.lenient).await,
+        Some(Command::Split(command)) => split(command, args.lenient).await,
+        None => {
+            let path = args.path.context("missing path of the HTTP Archive file")?;
+            let mut rules = sanitize::Rules::builtin();
+            if let Some(path) = &args.rules {
+                rules.extend(sanitize::Rules::from_file(path)?);
+            }
+            let (har, warnings) = load(&path, args.lenient).await?;
+            let mut app = app::App::init(har, warnings, rules);
+            run(&mut app).await
+        }
+    }
+}
+
+async fn load(path: &Path, lenient: bool) -> anyhow::Result<(Har, Vec<lenient::Warning>)> {
+    if lenient {
+        Har::from_file_lenient(path)
+            .await
+            .context("failed to parse HAR file")
+    } else {
+        let har = Har::from_file(path)
+            .await
+            .context("failed to parse HAR file (try --lenient to skip invalid parts)")?;
+        Ok((har, Vec::new()))
+    }
+}
+
+async fn sanitize(args: SanitizeArgs, lenient: bool) -> anyhow::Result<()> {
+    let (mut har, warnings) = load(&args.input, lenient).await?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let mut rules = if args.no_builtin {
+        sanitize::Rules::default()
+    } else {
+        sanitize::Rules::builtin()
+    };
+    if let Some(path) = &args.rules {
+        rules.extend(sanitize::Rules::from_file(path)?);
+    }
+    rules.extend(sanitize::Rules {
+        headers: args.header,
+        cookies: args.cookie,
+        query_params: args.query,
+        body_keys: args.body_key,
+    });
+
+    let mask = match &args.hash_key {
+        Some(key) => sanitize::Mask::keyed_hash(key),
+        None if args.hash => sanitize::Mask::random_hash()?,
+        None => sanitize::Mask::Redact,
+    };
+    let count = sanitize::sanitize(&mut har, &rules, mask);
+    har.to_file(&args.output)
+        .with_context(|| format!("failed to write {}", args.output.display()))?;
+    eprintln!("masked {} values", count);
 
     Ok(())
 }
 
+async fn export(args: ExportArgs, lenient: bool) -> anyhow::Result<()> {
+    let (har, warnings) = load(&args.input, lenient).await?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let filter = args
+        .filter
+        .as_deref()
+        .map(filter::Expr::parse)
+        .transpose()
+        .map_err(|err| anyhow::anyhow!("invalid filter expression: {}", err))?;
+    for n in &args.entry {
+        if *n == 0 || *n > har.log.entries.len() {
+            anyhow::bail!(
+                "entry {} does not exist, the file has {} entries",
+                n,
+                har.log.entries.len()
+            );
+        }
+    }
+
+    let snippets: Vec<String> = har
+        .log
+        .entries
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| args.entry.is_empty() || args.entry.contains(&(i + 1)))
+        .filter(|(_, entry)| filter.as_ref().is_none_or(|filter| filter.matches(entry)))
+        .map(|(_, entry)| args.format.render(entry))
+        .collect();
+    let text = snippets.join("\n\n") + "\n";
+    ignore_broken_pipe(std::io::stdout().lock().write_all(text.as_bytes()))
+}
+
+async fn list(args: ListArgs, lenient: bool) -> anyhow::Result<()> {
+    let (har, warnings) = load(&args.input, lenient).await?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let filter = args
+        .filter
+        .as_deref()
+        .map(filter::Expr::parse)
+        .transpose()
+        .map_err(|err| anyhow::anyhow!("invalid filter expression: {}", err))?;
+    let items = har.to_table_items();
+    let records: Vec<list::Record> = har
+        .to_view(filter.as_ref(), args.sort, args.reverse)
+        .into_iter()
+        .map(|i| items[i].to_record())
+        .collect();
+
+    let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+    ignore_broken_pipe(
+        list::write(&mut out, &records, args.format, !args.no_header).and_then(|()| out.flush()),
+    )
+}
+
+async fn stats(args: StatsArgs, lenient: bool) -> anyhow::Result<()> {
+    let (har, warnings) = load(&args.input, lenient).await?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    let filter = args
+        .filter
+        .as_deref()
+        .map(filter::Expr::parse)
+        .transpose()
+        .map_err(|err| anyhow::anyhow!("invalid filter expression: {}", err))?;
+    let view = har.to_view(filter.as_ref(), None, false);
+    let stats = stats::Stats::new(&har, &view, args.top);
+    let text = match args.format {
+        stats::OutputFormat::Text => stats.to_string(),
+        stats::OutputFormat::Json => serde_json::to_string_pretty(&stats)? + "\n",
+    };
+    ignore_broken_pipe(std::io::stdout().lock().write_all(text.as_bytes()))
+}
+
+async fn diff(args: DiffArgs, lenient: bool) -> anyhow::Result<()> {
+    let mut hars = Vec::new();
+    for path in [&args.before, &args.after] {
+        let (har, warnings) = load(path, lenient)
+            .await
+            .with_context(|| format!("failed to load {}", path.display()))?;
+        for warning in warnings {
+            eprintln!("warning: {}: {}", path.display(), warning);
+        }
+        hars.push(har);
+    }
+    let after = hars.pop().context("missing the later file")?;
+    let before = hars.pop().context("missing the earlier file")?;
+
+    let options = diff::Options {
+        ignore_query: args.ignore_query,
+        all_headers: args.all_headers,
+        min_time_delta: args.min_time_delta,
+    };
+    let diffs = diff::compare(&before, &after, &options);
+
+    if args.tui {
+        let mut app = app::DiffApp::init(
+            [
+                args.before.display().to_string(),
+                args.after.display().to_string(),
+            ],
+            before,
+            after,
+            diffs,
+            &options,
+        );
+        return run_diff(&mut app).await;
+    }
+
+    let report = diff::Report::new(
+        diff::FileSummary {
+            path: args.before.display().to_string(),
+            entries: before.log.entries.len(),
+        },
+        diff::FileSummary {
+            path: args.after.display().to_string(),
+            entries: after.log.entries.len(),
+        },
+        diffs,
+    );
+    let text = match args.format {
+        diff::OutputFormat::Text => report.to_string(),
+        diff::OutputFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+    };
+    ignore_broken_pipe(std::io::stdout().lock().write_all(text.as_bytes()))
+}
+
+async fn merge(args: MergeArgs, lenient: bool) -> anyhow::Result<()> {
+    let mut hars = Vec::new();
+    for path in &args.inputs {
+        let (har, warnings) = load(path, lenient)
+            .await
+            .with_context(|| format!("failed to load {}", path.display()))?;
+        for warning in warnings {
+            eprintln!("warning: {}: {}", path.display(), warning);
+        }
+        hars.push(har);
+    }
+
+    let merged = merge::merge(&hars);
+    merged
+        .har
+        .to_file(&args.output)
+        .with_context(|| format!("failed to write {}", args.output.display()))?;
+    eprintln!(
+        "merged {} files into {} entries and {} pages (left out {} duplicate entries and {} duplicate pages)",
+        hars.len(),
+        merged.har.log.entries.len(),
+        merged.har.log.pages.as_ref().map_or(0, Vec::len),
+        merged.duplicate_entries,
+        merged.duplicate_pages,
+    );
+
+    Ok(())
+}
+
+async fn split(args: SplitArgs, lenient: bool) -> anyhow::Result<()> {
+    let (har, warnings) = load(&args.input, lenient).await?;
+    for warning in warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    // Files read from stdin are named after the way they were split
+    let stem = match args.input.file_name().and_then(|name| name.to_str()) {
+        Some(name) if args.input != Path::new("-") => {
+            let name = name.trim_end_matches(".gz");
+            let name = name.trim_end_matches(".zst");
+            let name = name.trim_end_matches(".br");
+            name.trim_end_matches(".har").to_string()
+        }
+        _ => "split".to_string(),
+    };
+    std::fs::create_dir_all(&args.output)
+        .with_context(|| format!("failed to create {}", args.output.display()))?;
+
+    // Names that differ only in replaced characters or in case would be written to one file
+    let mut taken = std::collections::HashSet::new();
+    let mut parts = Vec::new();
+    for (name, part) in split::split(&har, args.by, args.window) {
+        let name: String = name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' => c,
+                _ => '_',
+            })
+            .collect();
+        let name = (1..)
+            .map(|n| match n {
+                1 => name.clone(),
+                n => format!("{}-{}", name, n),
+            })
+            .find(|name| taken.insert(name.to_lowercase()))
+            .context("no unique file name left")?;
+        parts.push((args.output.join(format!("{}.{}.har", stem, name)), part));
+    }
+
+    // Checked before writing anything, so a refused split leaves no files behind
+    if !args.force {
+        let existing: Vec<String> = parts
+            .iter()
+            .filter(|(path, _)| path.exists())
+            .map(|(path, _)| path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            anyhow::bail!(
+                "files of an earlier split exist, pass --force to replace them: {}",
+                existing.join(", ")
+            );
+        }
+    }
+
+    for (path, part) in parts {
+        let written = if args.force {
+            part.to_file(&path)
+        } else {
+            part.to_new_file(&path)
+        };
+        written.with_context(|| format!("failed to write {}", path.display()))?;
+        eprintln!(
+            "wrote {} ({} entries)",
+            path.display(),
+            part.log.entries.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Succeeds when the output was piped to a command that exited early, like `head`
+fn ignore_broken_pipe(result: std::io::Result<()>) -> anyhow::Result<()> {
+    match result {
+        Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => result.context("failed to write to the standard output"),
+    }
+}
+
 pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
     let backend = CrosstermBackend::new(std::io::stderr());
     let terminal = Terminal::new(backend)?;
@@ -47,15 +645,24 @@ pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
     tui.init()?;
 
     while app.running {
-        tui.draw(app)?;
+        tui.draw(|frame| ui::render(app, frame))?;
         match tui.events.next().await? {
             event::Event::Tick => app.tick(),
             event::Event::Key(key_event) => {
-                if let Some(command) = handler::handle_key_events(key_event) {
+                if let Some(command) = handler::handle_key_events(key_event, app) {
+                    command.exec(app);
+                }
+            }
+            event::Event::Paste(text) => {
+                if let Some(command) = handler::handle_paste_event(text, app) {
+                    command.exec(app);
+                }
+            }
+            event::Event::Mouse(mouse_event) => {
+                if let Some(command) = handler::handle_mouse_events(mouse_event, app) {
                     command.exec(app);
                 }
             }
-            event::Event::Mouse(_) => {}
             event::Event::Resize(_, _) => {}
         }
     }
@@ -63,3 +670,26 @@ pub async fn run(app: &mut app::App) -> anyhow::Result<()> {
     tui.exit()?;
     Ok(())
 }
+
+pub async fn run_diff(app: &mut app::DiffApp) -> anyhow::Result<()> {
+    let backend = CrosstermBackend::new(std::io::stderr());
+    let terminal = Terminal::new(backend)?;
+    let events = event::EventHandler::new(250);
+    let mut tui = tui::Tui::new(terminal, events);
+    tui.init()?;
+
+    while app.running {
+        tui.draw(|frame| ui::render_diff(app, frame))?;
+        let command = match tui.events.next().await? {
+            event::Event::Key(key_event) => handler::handle_diff_key_events(key_event),
+            event::Event::Mouse(mouse_event) => handler::handle_diff_mouse_events(mouse_event, app),
+            _ => None,
+        };
+        if let Some(command) = command {
+            command.exec(app);
+        }
+    }
+
+    tui.exit()?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Combining several HAR files into one, e.g. captures of one flow across browser sessions.
+
+use crate::har::{Entry, Har, Log, Page};
+use std::collections::HashMap;
+
+/// The combined file, with the number of duplicates left out
+#[derive(Debug)]
+pub struct Merged {
+    pub har: Har,
+    pub duplicate_pages: usize,
+    pub duplicate_entries: usize,
+}
+
+fn started(date_time: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    chrono::DateTime::parse_from_rfc3339(date_time).ok()
+}
+
+/// Merges the files into one, ordered by start time.
+///
+/// Pages with the same start time and title are the same page recorded twice, and are kept once.
+/// Browsers number pages from `page_1` in every file, so the pages are renumbered in order of
+/// their start time, and the `pageref` of every entry follows its page.
+/// Entries that are equal after that are kept once.
+/// The version, creator and browser are taken from the first file.
+pub fn merge(hars: &[Har]) -> Merged {
+    let mut pages: Vec<Page> = Vec::new();
+    // The position in `pages` of each page, by file and original id
+    let mut page_positions: HashMap<(usize, &str), usize> = HashMap::new();
+    let mut duplicate_pages = 0;
+    for (file, har) in hars.iter().enumerate() {
+        for page in har.log.pages.iter().flatten() {
+            let position = match pages.iter().position(|p| {
+                p.started_date_time == page.started_date_time && p.title == page.title
+            }) {
+                Some(position) => {
+                    duplicate_pages += 1;
+                    position
+                }
+                None => {
+                    pages.push(page.clone());
+                    pages.len() - 1
+                }
+            };
+            page_positions.insert((file, page.id.as_str()), position);
+        }
+    }
+
+    // The sort is stable, so pages that started at the same time stay in file order
+    let mut order: Vec<usize> = (0..pages.len()).collect();
+    order.sort_by_key(|i| started(&pages[*i].started_date_time));
+    let mut new_ids = vec![String::new(); pages.len()];
+    for (n, i) in order.iter().enumerate() {
+        new_ids[*i] = format!("page_{}", n + 1);
+    }
+
+    let mut entries: Vec<Entry> = Vec::new();
+    // Entries with the same start time, method and URL, which are compared in full
+    let mut candidates: HashMap<(String, String, String), Vec<usize>> = HashMap::new();
+    let mut duplicate_entries = 0;
+    for (file, har) in hars.iter().enumerate() {
+        for entry in &har.log.entries {
+            let mut entry = entry.clone();
+            // A `pageref` without a matching page is dropped rather than pointing elsewhere
+            entry.pageref = entry
+                .pageref
+                .as_deref()
+                .and_then(|id| page_positions.get(&(file, id)))
+                .map(|position| new_ids[*position].clone());
+
+            let key = (
+                entry.started_date_time.clone(),
+                entry.request.method.clone(),
+                entry.request.url.to_string(),
+            );
+            let same = candidates.entry(key).or_default();
+            if same.iter().any(|i| entries[*i] == entry) {
+                duplicate_entries += 1;
+                continue;
+            }
+            same.push(entries.len());
+            entries.push(entry);
+        }
+    }
+    entries.sort_by_key(|entry| started(&entry.started_date_time));
+
+    let pages: Vec<Page> = order
+        .into_iter()
+        .map(|i| Page {
+            id: new_ids[i].clone(),
+            ..pages[i].clone()
+        })
+        .collect();
+
+    let first = hars.first().map(|har| &har.log);
+    let har = Har {
+        log: Log {
+            version: first.and_then(|log| log.version.clone()),
+            creator: first.and_then(|log| log.creator.clone()),
+            browser: first.and_then(|log| log.browser.clone()),
+            pages: (!pages.is_empty()).then_some(pages),
+            entries,
+            comment: None,
+            custom: Default::default(),
+        },
+    };
+
+    Merged {
+        har,
+        duplicate_pages,
+        duplicate_entries,
+    }
+}
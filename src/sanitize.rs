@@ -0,0 +1,511 @@
+//! Masking of secrets in HAR files before they are shared.
+//!
+//! Header, cookie, query parameter and body key names are matched against [`Rules`],
+//! case-insensitively. A name containing `*` is matched as a glob.
+//! Matched values are replaced with a placeholder, or with a keyed hash of the value
+//! so the same secret can still be correlated across entries.
+
+use crate::body::{self, MimeKind};
+use crate::filter::Glob;
+use crate::har::{Cookie, Entry, Har, Header};
+use anyhow::Context;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use url::form_urlencoded;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Number of bytes of the HMAC-SHA256 digest kept in a hashed value
+const HASH_BYTES: usize = 8;
+
+/// Names of the values to mask, as read from a rules file:
+///
+/// ```json
+/// { "headers": ["x-session-*"], "cookies": ["sid"], "queryParams": ["t"], "bodyKeys": ["pin"] }
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct Rules {
+    pub headers: Vec<String>,
+    pub cookies: Vec<String>,
+    pub query_params: Vec<String>,
+    pub body_keys: Vec<String>,
+}
+
+impl Rules {
+    /// Credentials commonly found in browser traffic
+    pub fn builtin() -> Self {
+        fn strings(names: &[&str]) -> Vec<String> {
+            names.iter().map(|name| name.to_string()).collect()
+        }
+
+        Self {
+            headers: strings(&[
+                "authorization",
+                "proxy-authorization",
+                "x-api-key",
+                "x-auth-token",
+                "x-access-token",
+                "x-csrf-token",
+                "x-xsrf-token",
+                "x-amz-security-token",
+            ]),
+            cookies: strings(&["*"]),
+            query_params: strings(&[
+                "access_token",
+                "id_token",
+                "refresh_token",
+                "token",
+                "api_key",
+                "apikey",
+                "key",
+                "password",
+                "secret",
+                "client_secret",
+                "code",
+                "signature",
+                "sig",
+                "auth",
+                "x-amz-credential",
+                "x-amz-signature",
+                "x-amz-security-token",
+            ]),
+            body_keys: strings(&[
+                "password",
+                "passwd",
+                "pwd",
+                "secret",
+                "client_secret",
+                "token",
+                "access_token",
+                "refresh_token",
+                "id_token",
+                "api_key",
+                "apikey",
+                "authorization",
+                "card_number",
+                "cvv",
+            ]),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("failed to read rules file {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse rules file {}", path.display()))
+    }
+
+    pub fn extend(&mut self, other: Rules) {
+        self.headers.extend(other.headers);
+        self.cookies.extend(other.cookies);
+        self.query_params.extend(other.query_params);
+        self.body_keys.extend(other.body_keys);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mask {
+    /// Replace values with `[REDACTED]`
+    Redact,
+    /// Replace values with a truncated HMAC-SHA256 digest under the key, so equal values stay
+    /// equal. Without the key, short secrets such as PINs cannot be found by hashing guesses.
+    Hash([u8; 32]),
+}
+
+impl Mask {
+    /// Hashes with a key only known to this run
+    pub fn random_hash() -> anyhow::Result<Self> {
+        let mut key = [0; 32];
+        getrandom::getrandom(&mut key)
+            .map_err(|err| anyhow::anyhow!("failed to generate a hash key: {}", err))?;
+        Ok(Self::Hash(key))
+    }
+
+    /// Hashes with a key derived from a passphrase, so files sanitized with it can be correlated
+    pub fn keyed_hash(passphrase: &str) -> Self {
+        Self::Hash(Sha256::digest(passphrase.as_bytes()).into())
+    }
+
+    fn apply(self, value: &str) -> String {
+        match self {
+            Self::Redact => REDACTED.to_string(),
+            Self::Hash(key) => {
+                let mut mac =
+                    Hmac::<Sha256>::new_from_slice(&key).expect("HMAC accepts keys of any length");
+                mac.update(value.as_bytes());
+                let digest = mac.finalize().into_bytes();
+                let hex: String = digest[..HASH_BYTES]
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+                format!("hmac-sha256:{}", hex)
+            }
+        }
+    }
+}
+
+/// Masks the values matched by the rules in every entry, and returns how many were masked
+pub fn sanitize(har: &mut Har, rules: &Rules, mask: Mask) -> usize {
+    let mut sanitizer = Sanitizer::new(rules, mask);
+    // Chrome names pages after their URL
+    for page in har.log.pages.iter_mut().flatten() {
+        if let Some(title) = sanitizer.url(&page.title) {
+            page.title = title;
+        }
+    }
+    for entry in har.log.entries.iter_mut() {
+        sanitizer.entry(entry);
+    }
+    sanitizer.masked
+}
+
+/// Compiled names of one kind of value
+struct NameRules<'a> {
+    names: &'a [String],
+    globs: Vec<Glob>,
+}
+
+impl<'a> NameRules<'a> {
+    fn new(names: &'a [String]) -> Self {
+        Self {
+            names,
+            globs: names.iter().map(|name| Glob::new(name)).collect(),
+        }
+    }
+
+    /// Exact match, or glob match for names containing `*`
+    fn matches(&self, name: &str) -> bool {
+        self.names.iter().zip(&self.globs).any(|(pattern, glob)| {
+            if pattern.contains('*') {
+                glob.is_match(name)
+            } else {
+                pattern.eq_ignore_ascii_case(name)
+            }
+        })
+    }
+}
+
+struct Sanitizer<'a> {
+    headers: NameRules<'a>,
+    cookies: NameRules<'a>,
+    query_params: NameRules<'a>,
+    body_keys: NameRules<'a>,
+    mask: Mask,
+    masked: usize,
+}
+
+impl<'a> Sanitizer<'a> {
+    fn new(rules: &'a Rules, mask: Mask) -> Self {
+        Self {
+            headers: NameRules::new(&rules.headers),
+            cookies: NameRules::new(&rules.cookies),
+            query_params: NameRules::new(&rules.query_params),
+            body_keys: NameRules::new(&rules.body_keys),
+            mask,
+            masked: 0,
+        }
+    }
+
+    /// Masks a non-empty value that has not been masked yet
+    fn mask(&mut self, value: &str) -> Option<String> {
+        if value.is_empty() || value == REDACTED {
+            return None;
+        }
+        self.masked += 1;
+        Some(self.mask.apply(value))
+    }
+
+    fn mask_in_place(&mut self, value: &mut String) {
+        if let Some(masked) = self.mask(value) {
+            *value = masked;
+        }
+    }
+
+    fn entry(&mut self, entry: &mut Entry) {
+        let request = &mut entry.request;
+        self.headers(&mut request.headers);
+        self.cookies(&mut request.cookies);
+        for param in request.query_string.iter_mut() {
+            if self.query_params.matches(&param.name) {
+                self.mask_in_place(&mut param.value);
+            }
+        }
+        if let Some(url) = self.url(request.url.as_str()) {
+            if let Ok(url) = url::Url::parse(&url) {
+                request.url = url;
+            }
+        }
+        if let Some(post_data) = &mut request.post_data {
+            for param in post_data.params.iter_mut().flatten() {
+                if let Some(value) = &mut param.value {
+                    if self.body_keys.matches(&param.name) {
+                        self.mask_in_place(value);
+                    }
+                }
+            }
+            if let Some(text) = &mut post_data.text {
+                self.body(text, &post_data.mime_type);
+            }
+        }
+
+        let response = &mut entry.response;
+        self.headers(&mut response.headers);
+        self.cookies(&mut response.cookies);
+        if let Some(url) = self.url(&response.redirect_url) {
+            response.redirect_url = url;
+        }
+        let content = &mut response.content;
+        let mime_type = content.mime_type.clone().unwrap_or_default();
+        let is_base64 = content
+            .encoding
+            .as_deref()
+            .is_some_and(|encoding| encoding.eq_ignore_ascii_case("base64"));
+        if is_base64 {
+            if let Some(mut text) = body::content_text(content) {
+                if self.body(&mut text, &mime_type) {
+                    content.text = Some(base64::engine::general_purpose::STANDARD.encode(text));
+                }
+            }
+        } else if let Some(text) = &mut content.text {
+            self.body(text, &mime_type);
+        }
+    }
+
+    fn headers(&mut self, headers: &mut [Header]) {
+        for header in headers.iter_mut() {
+            let name = header.name.to_ascii_lowercase();
+            if self.headers.matches(&name) {
+                self.mask_in_place(&mut header.value);
+                continue;
+            }
+            let masked = match name.as_str() {
+                "cookie" => self.cookie_header(&header.value),
+                "set-cookie" => self.set_cookie_header(&header.value),
+                "referer" | "location" | "content-location" => self.url(&header.value),
+                _ => None,
+            };
+            if let Some(value) = masked {
+                header.value = value;
+            }
+        }
+    }
+
+    fn cookies(&mut self, cookies: &mut [Cookie]) {
+        for cookie in cookies.iter_mut() {
+            if self.cookies.matches(&cookie.name) {
+                self.mask_in_place(&mut cookie.value);
+            }
+        }
+    }
+
+    /// Masks the values in a `Cookie: a=1; b=2` header
+    fn cookie_header(&mut self, value: &str) -> Option<String> {
+        let mut changed = false;
+        let pairs: Vec<String> = value
+            .split(';')
+            .map(|pair| {
+                let pair = pair.trim();
+                match pair.split_once('=') {
+                    Some((name, value)) if self.cookies.matches(name) => match self.mask(value) {
+                        Some(masked) => {
+                            changed = true;
+                            format!("{}={}", name, masked)
+                        }
+                        None => pair.to_string(),
+                    },
+                    _ => pair.to_string(),
+                }
+            })
+            .collect();
+        changed.then(|| pairs.join("; "))
+    }
+
+    /// Masks the value in a `Set-Cookie: a=1; Path=/` header, keeping the attributes.
+    /// Firefox joins multiple cookies with newlines.
+    fn set_cookie_header(&mut self, value: &str) -> Option<String> {
+        let mut changed = false;
+        let lines: Vec<String> = value
+            .split('\n')
+            .map(|line| {
+                let (pair, attributes) = match line.split_once(';') {
+                    Some((pair, attributes)) => (pair, Some(attributes)),
+                    None => (line, None),
+                };
+                let masked = match pair.split_once('=') {
+                    Some((name, value)) if self.cookies.matches(name.trim()) => self
+                        .mask(value)
+                        .map(|masked| format!("{}={}", name, masked)),
+                    _ => None,
+                };
+                match (masked, attributes) {
+                    (Some(pair), Some(attributes)) => {
+                        changed = true;
+                        format!("{};{}", pair, attributes)
+                    }
+                    (Some(pair), None) => {
+                        changed = true;
+                        pair
+                    }
+                    (None, _) => line.to_string(),
+                }
+            })
+            .collect();
+        changed.then(|| lines.join("\n"))
+    }
+
+    /// Masks the query parameters and the parameters in the fragment of a URL, as in
+    /// `#access_token=...`. The URL may be relative, like `/next?token=...` in a `Location`.
+    fn url(&mut self, value: &str) -> Option<String> {
+        let (rest, fragment) = match value.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (value, None),
+        };
+        let (base, query) = match rest.split_once('?') {
+            Some((base, query)) => (base, Some(query)),
+            None => (rest, None),
+        };
+        let masked_query = query.and_then(|query| self.form(query, false));
+        let masked_fragment = fragment.and_then(|fragment| self.form(fragment, false));
+        if masked_query.is_none() && masked_fragment.is_none() {
+            return None;
+        }
+
+        let mut url = base.to_string();
+        if let Some(query) = masked_query.as_deref().or(query) {
+            url.push('?');
+            url.push_str(query);
+        }
+        if let Some(fragment) = masked_fragment.as_deref().or(fragment) {
+            url.push('#');
+            url.push_str(fragment);
+        }
+        Some(url)
+    }
+
+    /// Masks the values of an `application/x-www-form-urlencoded` string,
+    /// matched by body keys in a body and by query parameters in a URL
+    fn form(&mut self, text: &str, is_body: bool) -> Option<String> {
+        let mut changed = false;
+        let pairs: Vec<(String, String)> = form_urlencoded::parse(text.as_bytes())
+            .map(|(name, value)| {
+                let rules = if is_body {
+                    &self.body_keys
+                } else {
+                    &self.query_params
+                };
+                if rules.matches(&name) {
+                    if let Some(masked) = self.mask(&value) {
+                        changed = true;
+                        return (name.into_owned(), masked);
+                    }
+                }
+                (name.into_owned(), value.into_owned())
+            })
+            .collect();
+        changed.then(|| {
+            form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(pairs)
+                .finish()
+        })
+    }
+
+    /// Masks the body in place, and returns whether anything was masked
+    fn body(&mut self, text: &mut String, mime_type: &str) -> bool {
+        let essence = mime_type.split(';').next().unwrap_or_default().trim();
+        let masked = if essence.ends_with("json") {
+            self.json_text(text)
+        } else if essence == "application/x-www-form-urlencoded" {
+            self.form(text, true)
+        } else if let MimeKind::Multipart(Some(boundary)) = MimeKind::from_mime_type(mime_type) {
+            self.multipart(text, &boundary)
+        } else {
+            None
+        };
+        match masked {
+            Some(masked) => {
+                *text = masked;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Masks the values of the fields of a `multipart/form-data` body, matched by body keys.
+    /// Files are left as they are.
+    fn multipart(&mut self, text: &str, boundary: &str) -> Option<String> {
+        let delimiter = format!("--{}", boundary);
+        let mut sections: Vec<String> = text.split(delimiter.as_str()).map(String::from).collect();
+        let mut changed = false;
+        // Everything before the first delimiter is the preamble, and `--` starts the epilogue
+        for section in sections.iter_mut().skip(1) {
+            if section.starts_with("--") {
+                break;
+            }
+            let part = body::parse_multipart_part(section);
+            let matched = part.file_name.is_none()
+                && part
+                    .name
+                    .as_deref()
+                    .is_some_and(|name| self.body_keys.matches(name));
+            if !matched {
+                continue;
+            }
+            // The value follows the blank line after the headers of the part
+            let Some(start) = section
+                .find("\r\n\r\n")
+                .map(|i| i + 4)
+                .or_else(|| section.find("\n\n").map(|i| i + 2))
+            else {
+                continue;
+            };
+            if !section[start..].starts_with(&part.body) {
+                continue;
+            }
+            if let Some(masked) = self.mask(&part.body) {
+                section.replace_range(start..start + part.body.len(), &masked);
+                changed = true;
+            }
+        }
+        changed.then(|| sections.join(delimiter.as_str()))
+    }
+
+    fn json_text(&mut self, text: &str) -> Option<String> {
+        let mut value: Value = serde_json::from_str(text).ok()?;
+        let before = self.masked;
+        self.json(&mut value);
+        if self.masked == before {
+            return None;
+        }
+        serde_json::to_string(&value).ok()
+    }
+
+    /// Masks the values of matching keys at any depth.
+    /// Objects and arrays under a matching key are masked as a whole.
+    fn json(&mut self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if !self.body_keys.matches(key) {
+                        self.json(value);
+                        continue;
+                    }
+                    let text = match &*value {
+                        Value::Null => continue,
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    if let Some(masked) = self.mask(&text) {
+                        *value = Value::String(masked);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(|value| self.json(value)),
+            _ => {}
+        }
+    }
+}
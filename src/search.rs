@@ -0,0 +1,105 @@
+use crate::har::Entry;
+
+/// A query matched against the text of entries.
+/// Matching is case-insensitive unless the query contains an uppercase letter (smart case).
+#[derive(Debug, Clone)]
+pub struct Query {
+    text: String,
+    case_sensitive: bool,
+}
+
+impl Query {
+    pub fn new(text: &str) -> Self {
+        let case_sensitive = text.chars().any(|c| c.is_uppercase());
+        Self {
+            text: if case_sensitive {
+                text.to_string()
+            } else {
+                text.to_lowercase()
+            },
+            case_sensitive,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn is_match(&self, haystack: &str) -> bool {
+        if self.case_sensitive {
+            haystack.contains(&self.text)
+        } else {
+            haystack.to_lowercase().contains(&self.text)
+        }
+    }
+
+    /// Returns the byte ranges of every occurrence of the query in the haystack
+    pub fn find_ranges(&self, haystack: &str) -> Vec<(usize, usize)> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        if self.case_sensitive {
+            return haystack
+                .match_indices(&self.text)
+                .map(|(start, matched)| (start, start + matched.len()))
+                .collect();
+        }
+
+        // Lowercasing may change the length of non-ASCII characters anywhere in the text,
+        // so each byte of the folded text remembers the character of the haystack it came from
+        let mut folded = String::with_capacity(haystack.len());
+        let mut origins: Vec<(usize, usize)> = Vec::with_capacity(haystack.len());
+        for (start, c) in haystack.char_indices() {
+            let origin = (start, start + c.len_utf8());
+            for lower in c.to_lowercase() {
+                folded.push(lower);
+                origins.resize(folded.len(), origin);
+            }
+        }
+
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for (start, matched) in folded.match_indices(&self.text) {
+            let (start, end) = (origins[start].0, origins[start + matched.len() - 1].1);
+            // Matches inside the expansion of one character share it
+            match ranges.last_mut() {
+                Some(last) if start < last.1 => last.1 = last.1.max(end),
+                _ => ranges.push((start, end)),
+            }
+        }
+        ranges
+    }
+
+    pub fn matches_entry(&self, entry: &Entry) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let request = &entry.request;
+        let response = &entry.response;
+
+        self.is_match(request.url.as_str())
+            || self.is_match(&request.method)
+            || self.is_match(&response.status.to_string())
+            || response
+                .content
+                .mime_type
+                .as_deref()
+                .is_some_and(|mime_type| self.is_match(mime_type))
+            || request
+                .headers
+                .iter()
+                .chain(response.headers.iter())
+                .any(|header| self.is_match(&header.name) || self.is_match(&header.value))
+            || request
+                .post_data
+                .as_ref()
+                .and_then(|post_data| post_data.text.as_deref())
+                .is_some_and(|text| self.is_match(text))
+            || response
+                .content
+                .text
+                .as_deref()
+                .is_some_and(|text| self.is_match(text))
+    }
+}
@@ -0,0 +1,1036 @@
+//! Command lines and code that reproduce the request of an entry.
+
+use crate::har::{Entry, Param, Request};
+use serde_json::Value;
+use std::fmt;
+use url::form_urlencoded;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Format {
+    Curl,
+    Httpie,
+    Wget,
+    #[value(name = "powershell")]
+    PowerShell,
+    Fetch,
+    Python,
+    Go,
+    Rust,
+}
+
+pub const FORMATS: [Format; 8] = [
+    Format::Curl,
+    Format::Httpie,
+    Format::Wget,
+    Format::PowerShell,
+    Format::Fetch,
+    Format::Python,
+    Format::Go,
+    Format::Rust,
+];
+
+impl Format {
+    pub fn render(self, entry: &Entry) -> String {
+        let parts = RequestParts::new(&entry.request);
+        match self {
+            Self::Curl => curl(&parts),
+            Self::Httpie => httpie(&parts),
+            Self::Wget => wget(&parts),
+            Self::PowerShell => powershell(&parts),
+            Self::Fetch => fetch(&parts),
+            Self::Python => python(&parts),
+            Self::Go => go(&parts),
+            Self::Rust => rust(&parts),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Curl => write!(f, "cURL"),
+            Self::Httpie => write!(f, "HTTPie"),
+            Self::Wget => write!(f, "wget"),
+            Self::PowerShell => write!(f, "PowerShell"),
+            Self::Fetch => write!(f, "JavaScript fetch"),
+            Self::Python => write!(f, "Python requests"),
+            Self::Go => write!(f, "Go net/http"),
+            Self::Rust => write!(f, "Rust reqwest"),
+        }
+    }
+}
+
+/// Content codings `curl --compressed` can decode
+const COMPRESSED_ENCODINGS: [&str; 5] = ["gzip", "deflate", "br", "zstd", "compress"];
+
+/// The parts of a request needed to reproduce it, with the headers a client computes dropped
+struct RequestParts<'a> {
+    method: &'a str,
+    url: &'a url::Url,
+    headers: Vec<(String, String)>,
+    /// Value of the `Cookie` header, or the cookies joined into one
+    cookie: Option<String>,
+    /// Whether the request accepted compressed responses. The `Accept-Encoding` header is
+    /// dropped then, since clients send the codings they can decode by themselves.
+    compressed: bool,
+    body: Body<'a>,
+}
+
+enum Body<'a> {
+    None,
+    Text(&'a str),
+    Json {
+        text: &'a str,
+        value: Value,
+    },
+    /// `application/x-www-form-urlencoded` fields, with the encoded text if it was recorded
+    Form {
+        text: Option<&'a str>,
+        pairs: Vec<(String, String)>,
+    },
+    Multipart(&'a [Param]),
+}
+
+impl Body<'_> {
+    fn from_request(request: &Request) -> Body<'_> {
+        let Some(post_data) = &request.post_data else {
+            return Body::None;
+        };
+        let mime_type = post_data.mime_type.to_ascii_lowercase();
+        let text = post_data.text.as_deref().filter(|text| !text.is_empty());
+        let params = post_data
+            .params
+            .as_deref()
+            .filter(|params| !params.is_empty());
+
+        if let (true, Some(params)) = (mime_type.starts_with("multipart/form-data"), params) {
+            return Body::Multipart(params);
+        }
+        if mime_type.starts_with("application/x-www-form-urlencoded") {
+            let pairs: Vec<(String, String)> = match (params, text) {
+                (Some(params), _) => params
+                    .iter()
+                    .map(|param| (param.name.clone(), param.value.clone().unwrap_or_default()))
+                    .collect(),
+                (None, Some(text)) => form_urlencoded::parse(text.as_bytes())
+                    .into_owned()
+                    .collect(),
+                (None, None) => return Body::None,
+            };
+            return Body::Form { text, pairs };
+        }
+
+        match (text, params) {
+            (Some(text), _) => match serde_json::from_str(text) {
+                Ok(value) if mime_type.contains("json") => Body::Json { text, value },
+                _ => Body::Text(text),
+            },
+            (None, Some(params)) => Body::Multipart(params),
+            (None, None) => Body::None,
+        }
+    }
+
+    /// The body as recorded, or encoded from the fields of a form
+    fn text(&self) -> Option<String> {
+        match self {
+            Self::None | Self::Multipart(_) => None,
+            Self::Text(text) | Self::Json { text, .. } => Some(text.to_string()),
+            Self::Form {
+                text: Some(text), ..
+            } => Some(text.to_string()),
+            Self::Form { text: None, pairs } => Some(
+                form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(pairs)
+                    .finish(),
+            ),
+        }
+    }
+
+    fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+impl<'a> RequestParts<'a> {
+    fn new(request: &'a Request) -> Self {
+        let compressed = request.headers.iter().any(|header| {
+            header.name.eq_ignore_ascii_case("accept-encoding")
+                && header.value.split(',').any(|coding| {
+                    COMPRESSED_ENCODINGS.contains(&coding.trim().to_ascii_lowercase().as_str())
+                })
+        });
+
+        let cookie = request
+            .headers
+            .iter()
+            .find(|header| header.name.eq_ignore_ascii_case("cookie"))
+            .map(|header| header.value.clone())
+            .or_else(|| {
+                (!request.cookies.is_empty()).then(|| {
+                    request
+                        .cookies
+                        .iter()
+                        .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                        .collect::<Vec<String>>()
+                        .join("; ")
+                })
+            });
+
+        let body = Body::from_request(request);
+        let is_multipart = matches!(body, Body::Multipart(_));
+
+        let mut headers: Vec<(String, String)> = request
+            .headers
+            .iter()
+            .filter(|header| {
+                let name = header.name.to_ascii_lowercase();
+                // HTTP/2 pseudo-headers, and headers the client sets by itself.
+                // A multipart body gets a new boundary, so its content type is set by the client.
+                !name.starts_with(':')
+                    && name != "cookie"
+                    && name != "content-length"
+                    && name != "host"
+                    && !(compressed && name == "accept-encoding")
+                    && !(is_multipart && name == "content-type")
+            })
+            .map(|header| (header.name.clone(), header.value.clone()))
+            .collect();
+
+        let has_content_type = request
+            .headers
+            .iter()
+            .any(|header| header.name.eq_ignore_ascii_case("content-type"));
+        if let Some(post_data) = &request.post_data {
+            if !has_content_type
+                && !post_data.mime_type.is_empty()
+                && !body.is_none()
+                && !is_multipart
+            {
+                headers.push(("Content-Type".to_string(), post_data.mime_type.clone()));
+            }
+        }
+
+        Self {
+            method: &request.method,
+            url: &request.url,
+            headers,
+            cookie,
+            compressed,
+            body,
+        }
+    }
+
+    /// The method a client uses when none is given: GET, or POST when there is a body
+    fn has_implied_method(&self) -> bool {
+        let implied = if self.body.is_none() { "GET" } else { "POST" };
+        self.method == implied
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The headers with repeated names merged into one, for clients that take a map.
+    /// Names are compared case-insensitively, and values are joined with `, ` as HTTP allows.
+    fn merged_headers(&self) -> Vec<(String, String)> {
+        let mut merged: Vec<(String, String)> = Vec::new();
+        for (name, value) in &self.headers {
+            match merged
+                .iter_mut()
+                .find(|(other, _)| other.eq_ignore_ascii_case(name))
+            {
+                Some((_, merged_value)) => {
+                    merged_value.push_str(", ");
+                    merged_value.push_str(value);
+                }
+                None => merged.push((name.clone(), value.clone())),
+            }
+        }
+        merged
+    }
+
+    /// The cookies with one value per name, for clients that take a map.
+    /// Browsers send the cookie with the most specific path first, which servers usually read.
+    fn unique_cookie_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = self.cookie_pairs();
+        let mut seen = std::collections::HashSet::new();
+        pairs.retain(|(name, _)| seen.insert(name.clone()));
+        pairs
+    }
+
+    /// The cookies as name and value pairs
+    fn cookie_pairs(&self) -> Vec<(String, String)> {
+        self.cookie
+            .iter()
+            .flat_map(|cookie| cookie.split(';'))
+            .filter_map(|pair| {
+                let (name, value) = pair.split_once('=')?;
+                Some((name.trim().to_string(), value.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// The URL without the query string, and the decoded query parameters
+    fn url_and_query(&self) -> (String, Vec<(String, String)>) {
+        let mut url = self.url.clone();
+        let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        url.set_query(None);
+        (url.to_string(), query)
+    }
+}
+
+/// Quotes the string for POSIX shells
+pub fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c))
+    {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Quotes the string as a double-quoted literal, which is valid in JavaScript, Python and Go
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+fn powershell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn rust_quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Indents every line but the first, for values embedded in an indented line
+fn indent_rest(text: &str, indent: &str) -> String {
+    text.replace('\n', &format!("\n{}", indent))
+}
+
+/// Indents every line, for statements in a function body
+fn indent_lines(text: &str, indent: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", indent, line))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Formats a command line with the first argument next to the command and the others on
+/// their own lines
+fn command_lines(command: &str, arguments: Vec<String>, continuation: &str) -> String {
+    let mut arguments = arguments.into_iter();
+    let first = arguments
+        .next()
+        .map(|argument| format!("{} {}", command, argument))
+        .unwrap_or_else(|| command.to_string());
+    std::iter::once(first)
+        .chain(arguments)
+        .collect::<Vec<String>>()
+        .join(&format!(" {}\n  ", continuation))
+}
+
+fn curl(parts: &RequestParts) -> String {
+    // `-g` keeps curl from reading `[]` and `{}` in the URL, as in `filter[name]=x`, as globs
+    let mut arguments = vec![shell_quote(parts.url.as_str()), "-g".to_string()];
+
+    // With `-X HEAD`, curl waits for a body that never comes; `-I` tells it not to read one
+    if parts.method == "HEAD" && parts.body.is_none() {
+        arguments.push("-I".to_string());
+    } else if !parts.has_implied_method() {
+        arguments.push(format!("-X {}", shell_quote(parts.method)));
+    }
+    for (name, value) in &parts.headers {
+        // `Name:` would make curl leave the header out, `Name;` sends it with an empty value
+        let header = if value.is_empty() {
+            format!("{};", name)
+        } else {
+            format!("{}: {}", name, value)
+        };
+        arguments.push(format!("-H {}", shell_quote(&header)));
+    }
+    if let Some(cookie) = &parts.cookie {
+        arguments.push(format!("-b {}", shell_quote(cookie)));
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Form { text: None, pairs } => {
+            for (name, value) in pairs {
+                arguments.push(format!(
+                    "--data-urlencode {}",
+                    shell_quote(&format!("{}={}", name, value))
+                ));
+            }
+        }
+        Body::Multipart(params) => {
+            for param in params.iter() {
+                // `-F` reads files from `@` values, `--form-string` takes values literally
+                let argument = match &param.file_name {
+                    Some(file_name) => {
+                        let mut field = format!("{}=@{}", param.name, file_name);
+                        if let Some(content_type) = &param.content_type {
+                            field.push_str(&format!(";type={}", content_type));
+                        }
+                        format!("-F {}", shell_quote(&field))
+                    }
+                    None => format!(
+                        "--form-string {}",
+                        shell_quote(&format!(
+                            "{}={}",
+                            param.name,
+                            param.value.as_deref().unwrap_or_default()
+                        ))
+                    ),
+                };
+                arguments.push(argument);
+            }
+        }
+        body => {
+            let text = body.text().unwrap_or_default();
+            arguments.push(format!("--data-raw {}", shell_quote(&text)));
+        }
+    }
+
+    if parts.compressed {
+        arguments.push("--compressed".to_string());
+    }
+
+    command_lines("curl", arguments, "\\")
+}
+
+fn httpie(parts: &RequestParts) -> String {
+    let mut arguments = Vec::new();
+    match parts.body {
+        Body::Form { .. } => arguments.push("--form".to_string()),
+        Body::Multipart(_) => arguments.push("--multipart".to_string()),
+        _ => {}
+    }
+    arguments.push(shell_quote(parts.method));
+    arguments.push(shell_quote(parts.url.as_str()));
+
+    for (name, value) in &parts.headers {
+        // `Name;` sends a header with an empty value, `Name:` would leave it out
+        let item = if value.is_empty() {
+            format!("{};", name)
+        } else {
+            format!("{}:{}", name, value)
+        };
+        arguments.push(shell_quote(&item));
+    }
+    if let Some(cookie) = &parts.cookie {
+        arguments.push(shell_quote(&format!("Cookie:{}", cookie)));
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Form { pairs, .. } => {
+            for (name, value) in pairs {
+                arguments.push(shell_quote(&format!("{}={}", name, value)));
+            }
+        }
+        Body::Multipart(params) => {
+            for param in params.iter() {
+                let item = match &param.file_name {
+                    Some(file_name) => {
+                        let mut item = format!("{}@{}", param.name, file_name);
+                        if let Some(content_type) = &param.content_type {
+                            item.push_str(&format!(";type={}", content_type));
+                        }
+                        item
+                    }
+                    None => format!(
+                        "{}={}",
+                        param.name,
+                        param.value.as_deref().unwrap_or_default()
+                    ),
+                };
+                arguments.push(shell_quote(&item));
+            }
+        }
+        body => {
+            let text = body.text().unwrap_or_default();
+            arguments.push(format!("--raw {}", shell_quote(&text)));
+        }
+    }
+
+    command_lines("http", arguments, "\\")
+}
+
+fn wget(parts: &RequestParts) -> String {
+    let mut lines = Vec::new();
+    let mut arguments = vec![
+        shell_quote(parts.url.as_str()),
+        "--output-document=-".to_string(),
+    ];
+
+    if parts.method != "GET" || !parts.body.is_none() {
+        arguments.push(format!("--method={}", shell_quote(parts.method)));
+    }
+    for (name, value) in &parts.headers {
+        arguments.push(format!(
+            "--header={}",
+            shell_quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(cookie) = &parts.cookie {
+        arguments.push(format!(
+            "--header={}",
+            shell_quote(&format!("Cookie: {}", cookie))
+        ));
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Multipart(_) => lines.push(
+            "# wget cannot build multipart bodies, save the body to a file and pass it with --body-file"
+                .to_string(),
+        ),
+        body => {
+            let text = body.text().unwrap_or_default();
+            arguments.push(format!("--body-data={}", shell_quote(&text)));
+        }
+    }
+
+    if parts.compressed {
+        arguments.push("--compression=auto".to_string());
+    }
+
+    lines.push(command_lines("wget", arguments, "\\"));
+    lines.join("\n")
+}
+
+fn powershell(parts: &RequestParts) -> String {
+    let mut lines =
+        vec!["$session = New-Object Microsoft.PowerShell.Commands.WebRequestSession".to_string()];
+    if let Some(user_agent) = parts.header("user-agent") {
+        lines.push(format!(
+            "$session.UserAgent = {}",
+            powershell_quote(user_agent)
+        ));
+    }
+    let host = parts.url.host_str().unwrap_or_default();
+    // A cookie added with the name and path of an earlier one replaces it
+    for (name, value) in parts.unique_cookie_pairs() {
+        lines.push(format!(
+            "$session.Cookies.Add((New-Object System.Net.Cookie({}, {}, '/', {})))",
+            powershell_quote(&name),
+            powershell_quote(&value),
+            powershell_quote(host)
+        ));
+    }
+
+    let mut arguments = vec![
+        "-UseBasicParsing".to_string(),
+        format!("-Uri {}", powershell_quote(parts.url.as_str())),
+        format!("-Method {}", powershell_quote(parts.method)),
+        "-WebSession $session".to_string(),
+    ];
+
+    // Windows PowerShell only accepts these headers as parameters
+    let headers: Vec<String> = parts
+        .merged_headers()
+        .iter()
+        .filter(|(name, _)| {
+            !name.eq_ignore_ascii_case("user-agent") && !name.eq_ignore_ascii_case("content-type")
+        })
+        .map(|(name, value)| {
+            format!(
+                "    {} = {}",
+                powershell_quote(name),
+                powershell_quote(value)
+            )
+        })
+        .collect();
+    if !headers.is_empty() {
+        arguments.push(format!("-Headers @{{\n{}\n  }}", headers.join("\n")));
+    }
+    if let Some(content_type) = parts.header("content-type") {
+        arguments.push(format!("-ContentType {}", powershell_quote(content_type)));
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Multipart(params) => {
+            // `-Form` needs PowerShell 6.1 or later. Keys of a hashtable literal must be unique
+            // regardless of case, so the values of a repeated field are given as an array.
+            let mut fields: Vec<(&str, Vec<String>)> = Vec::new();
+            for param in params.iter() {
+                let value = match &param.file_name {
+                    Some(file_name) => {
+                        format!("(Get-Item -Path {})", powershell_quote(file_name))
+                    }
+                    None => powershell_quote(param.value.as_deref().unwrap_or_default()),
+                };
+                match fields
+                    .iter_mut()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(&param.name))
+                {
+                    Some((_, values)) => values.push(value),
+                    None => fields.push((&param.name, vec![value])),
+                }
+            }
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(name, values)| {
+                    format!("    {} = {}", powershell_quote(name), values.join(", "))
+                })
+                .collect();
+            arguments.push(format!("-Form @{{\n{}\n  }}", fields.join("\n")));
+        }
+        body => {
+            let text = body.text().unwrap_or_default();
+            arguments.push(format!("-Body {}", powershell_quote(&text)));
+        }
+    }
+
+    lines.push(command_lines("Invoke-WebRequest", arguments, "`"));
+    lines.join("\n")
+}
+
+fn fetch(parts: &RequestParts) -> String {
+    let mut lines = Vec::new();
+    let mut options = Vec::new();
+
+    if parts.method != "GET" {
+        options.push(format!("  method: {}", quote(parts.method)));
+    }
+
+    let mut headers = parts.headers.clone();
+    if let Some(cookie) = &parts.cookie {
+        // Browsers ignore this header, and send their own cookies with `credentials: "include"`
+        headers.push(("Cookie".to_string(), cookie.clone()));
+    }
+    if !headers.is_empty() {
+        let entries: Vec<String> = headers
+            .iter()
+            .map(|(name, value)| format!("    {}: {}", quote(name), quote(value)))
+            .collect();
+        options.push(format!("  headers: {{\n{}\n  }}", entries.join(",\n")));
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Json { value, .. } => {
+            let json = serde_json::to_string_pretty(value).unwrap_or_default();
+            options.push(format!(
+                "  body: JSON.stringify({})",
+                indent_rest(&json, "  ")
+            ));
+        }
+        Body::Form { pairs, .. } => {
+            let entries: Vec<String> = pairs
+                .iter()
+                .map(|(name, value)| format!("    [{}, {}]", quote(name), quote(value)))
+                .collect();
+            options.push(format!(
+                "  body: new URLSearchParams([\n{}\n  ])",
+                entries.join(",\n")
+            ));
+        }
+        Body::Multipart(params) => {
+            lines.push("const body = new FormData();".to_string());
+            for param in params.iter() {
+                lines.push(match &param.file_name {
+                    Some(file_name) => format!(
+                        "body.append({}, new Blob([/* contents of {} */]{}), {});",
+                        quote(&param.name),
+                        file_name,
+                        param
+                            .content_type
+                            .as_deref()
+                            .map(|content_type| format!(", {{ type: {} }}", quote(content_type)))
+                            .unwrap_or_default(),
+                        quote(file_name)
+                    ),
+                    None => format!(
+                        "body.append({}, {});",
+                        quote(&param.name),
+                        quote(param.value.as_deref().unwrap_or_default())
+                    ),
+                });
+            }
+            lines.push(String::new());
+            options.push("  body".to_string());
+        }
+        Body::Text(text) => options.push(format!("  body: {}", quote(text))),
+    }
+
+    if options.is_empty() {
+        lines.push(format!("fetch({});", quote(parts.url.as_str())));
+    } else {
+        lines.push(format!(
+            "fetch({}, {{\n{}\n}});",
+            quote(parts.url.as_str()),
+            options.join(",\n")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Formats the pairs as a dict, or as a list of tuples if a name is repeated
+fn python_pairs(pairs: &[(String, String)]) -> String {
+    let repeated = pairs
+        .iter()
+        .enumerate()
+        .any(|(i, (name, _))| pairs[..i].iter().any(|(other, _)| other == name));
+    let entries: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| {
+            if repeated {
+                format!("    ({}, {}),", quote(name), quote(value))
+            } else {
+                format!("    {}: {},", quote(name), quote(value))
+            }
+        })
+        .collect();
+    let (open, close) = if repeated { ("[", "]") } else { ("{", "}") };
+    format!("{}\n{}\n{}", open, entries.join("\n"), close)
+}
+
+fn python_literal(value: &Value, indent: usize) -> String {
+    let padding = "    ".repeat(indent + 1);
+    let closing = "    ".repeat(indent);
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::String(s) => quote(s),
+        Value::Array(values) if values.is_empty() => "[]".to_string(),
+        Value::Array(values) => {
+            let items: Vec<String> = values
+                .iter()
+                .map(|value| format!("{}{},", padding, python_literal(value, indent + 1)))
+                .collect();
+            format!("[\n{}\n{}]", items.join("\n"), closing)
+        }
+        Value::Object(map) if map.is_empty() => "{}".to_string(),
+        Value::Object(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{}{}: {},",
+                        padding,
+                        quote(key),
+                        python_literal(value, indent + 1)
+                    )
+                })
+                .collect();
+            format!("{{\n{}\n{}}}", items.join("\n"), closing)
+        }
+    }
+}
+
+fn python(parts: &RequestParts) -> String {
+    let (url, query) = parts.url_and_query();
+    let mut lines = vec![
+        "import requests".to_string(),
+        String::new(),
+        format!("url = {}", quote(&url)),
+    ];
+    let mut arguments = vec!["url".to_string()];
+
+    if !query.is_empty() {
+        lines.push(format!("params = {}", python_pairs(&query)));
+        arguments.push("params=params".to_string());
+    }
+    if !parts.headers.is_empty() {
+        lines.push(format!(
+            "headers = {}",
+            python_pairs(&parts.merged_headers())
+        ));
+        arguments.push("headers=headers".to_string());
+    }
+    let cookies = parts.unique_cookie_pairs();
+    if !cookies.is_empty() {
+        lines.push(format!("cookies = {}", python_pairs(&cookies)));
+        arguments.push("cookies=cookies".to_string());
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Json { value, .. } => {
+            lines.push(format!("json_data = {}", python_literal(value, 0)));
+            arguments.push("json=json_data".to_string());
+        }
+        Body::Form { pairs, .. } => {
+            lines.push(format!("data = {}", python_pairs(pairs)));
+            arguments.push("data=data".to_string());
+        }
+        Body::Multipart(params) => {
+            let fields: Vec<(String, String)> = params
+                .iter()
+                .filter(|param| param.file_name.is_none())
+                .map(|param| (param.name.clone(), param.value.clone().unwrap_or_default()))
+                .collect();
+            if !fields.is_empty() {
+                lines.push(format!("data = {}", python_pairs(&fields)));
+                arguments.push("data=data".to_string());
+            }
+            let files: Vec<String> = params
+                .iter()
+                .filter_map(|param| {
+                    let file_name = param.file_name.as_ref()?;
+                    let content_type = param
+                        .content_type
+                        .as_deref()
+                        .map(|content_type| format!(", {}", quote(content_type)))
+                        .unwrap_or_default();
+                    Some(format!(
+                        "    {}: ({}, open({}, \"rb\"){}),",
+                        quote(&param.name),
+                        quote(file_name),
+                        quote(file_name),
+                        content_type
+                    ))
+                })
+                .collect();
+            if !files.is_empty() {
+                lines.push(format!("files = {{\n{}\n}}", files.join("\n")));
+                arguments.push("files=files".to_string());
+            }
+        }
+        Body::Text(text) => {
+            lines.push(format!("data = {}", quote(text)));
+            arguments.push("data=data".to_string());
+        }
+    }
+
+    let function = match parts.method {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" | "OPTIONS" => {
+            format!("requests.{}", parts.method.to_ascii_lowercase())
+        }
+        method => {
+            arguments.insert(0, quote(method));
+            "requests.request".to_string()
+        }
+    };
+    lines.push(String::new());
+    lines.push(format!(
+        "response = {}(\n    {},\n)",
+        function,
+        arguments.join(",\n    ")
+    ));
+    lines.push("print(response.status_code)".to_string());
+    lines.push("print(response.text)".to_string());
+    lines.join("\n")
+}
+
+/// Quotes the string as a Go raw string literal when it spans lines
+fn go_quote(s: &str) -> String {
+    // Raw string literals cannot contain backquotes, and drop carriage returns
+    if s.contains('\n') && !s.contains('`') && !s.contains('\r') {
+        format!("`{}`", s)
+    } else {
+        quote(s)
+    }
+}
+
+const GO_CHECK_ERR: &str = "if err != nil {\n\tpanic(err)\n}";
+
+fn go(parts: &RequestParts) -> String {
+    let mut imports = vec!["fmt", "io", "net/http"];
+    let mut statements: Vec<String> = Vec::new();
+    let mut content_type = None;
+
+    let body = match &parts.body {
+        Body::None => "nil",
+        Body::Form { pairs, .. } => {
+            imports.extend(["net/url", "strings"]);
+            statements.push("form := url.Values{}".to_string());
+            for (name, value) in pairs {
+                statements.push(format!("form.Add({}, {})", quote(name), quote(value)));
+            }
+            statements.push("body := strings.NewReader(form.Encode())".to_string());
+            "body"
+        }
+        Body::Multipart(params) => {
+            imports.extend(["bytes", "mime/multipart"]);
+            statements.push("body := &bytes.Buffer{}".to_string());
+            statements.push("writer := multipart.NewWriter(body)".to_string());
+            for param in params.iter() {
+                match &param.file_name {
+                    Some(file_name) => {
+                        imports.push("os");
+                        // A block of its own, so `file` and `part` can be declared again
+                        let block = [
+                            format!("file, err := os.Open({})", quote(file_name)),
+                            GO_CHECK_ERR.to_string(),
+                            "defer file.Close()".to_string(),
+                            format!(
+                                "part, err := writer.CreateFormFile({}, {})",
+                                quote(&param.name),
+                                quote(file_name)
+                            ),
+                            GO_CHECK_ERR.to_string(),
+                            "if _, err := io.Copy(part, file); err != nil {\n\tpanic(err)\n}"
+                                .to_string(),
+                        ];
+                        statements
+                            .push(format!("{{\n{}\n}}", indent_lines(&block.join("\n"), "\t")));
+                    }
+                    None => statements.push(format!(
+                        "if err := writer.WriteField({}, {}); err != nil {{\n\tpanic(err)\n}}",
+                        quote(&param.name),
+                        quote(param.value.as_deref().unwrap_or_default())
+                    )),
+                }
+            }
+            statements.push("if err := writer.Close(); err != nil {\n\tpanic(err)\n}".to_string());
+            content_type = Some("writer.FormDataContentType()");
+            "body"
+        }
+        body => {
+            imports.push("strings");
+            statements.push(format!(
+                "body := strings.NewReader({})",
+                go_quote(&body.text().unwrap_or_default())
+            ));
+            "body"
+        }
+    };
+    imports.sort_unstable();
+    imports.dedup();
+
+    statements.push(format!(
+        "req, err := http.NewRequest({}, {}, {})",
+        quote(parts.method),
+        quote(parts.url.as_str()),
+        body
+    ));
+    statements.push(GO_CHECK_ERR.to_string());
+    for (name, value) in &parts.headers {
+        statements.push(format!("req.Header.Add({}, {})", quote(name), quote(value)));
+    }
+    if let Some(content_type) = content_type {
+        statements.push(format!(
+            "req.Header.Set(\"Content-Type\", {})",
+            content_type
+        ));
+    }
+    if let Some(cookie) = &parts.cookie {
+        statements.push(format!("req.Header.Set(\"Cookie\", {})", quote(cookie)));
+    }
+    statements.extend([
+        "resp, err := http.DefaultClient.Do(req)".to_string(),
+        GO_CHECK_ERR.to_string(),
+        "defer resp.Body.Close()".to_string(),
+        "respBody, err := io.ReadAll(resp.Body)".to_string(),
+        GO_CHECK_ERR.to_string(),
+        "fmt.Printf(\"%s\\n\", respBody)".to_string(),
+    ]);
+
+    let imports: Vec<String> = imports.iter().map(|import| quote(import)).collect();
+    format!(
+        "package main\n\nimport (\n{}\n)\n\nfunc main() {{\n{}\n}}",
+        indent_lines(&imports.join("\n"), "\t"),
+        indent_lines(&statements.join("\n"), "\t")
+    )
+}
+
+fn rust(parts: &RequestParts) -> String {
+    let mut features = vec!["\"blocking\""];
+    let mut dependencies = String::new();
+    let mut statements = vec!["let client = reqwest::blocking::Client::new();".to_string()];
+
+    let request = match parts.method {
+        "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" => format!(
+            "client.{}({})",
+            parts.method.to_ascii_lowercase(),
+            rust_quote(parts.url.as_str())
+        ),
+        method => format!(
+            "client.request(reqwest::Method::from_bytes(b{})?, {})",
+            rust_quote(method),
+            rust_quote(parts.url.as_str())
+        ),
+    };
+    let mut calls = vec![request];
+    for (name, value) in &parts.headers {
+        calls.push(format!(
+            ".header({}, {})",
+            rust_quote(name),
+            rust_quote(value)
+        ));
+    }
+    if let Some(cookie) = &parts.cookie {
+        calls.push(format!(".header(\"Cookie\", {})", rust_quote(cookie)));
+    }
+
+    match &parts.body {
+        Body::None => {}
+        Body::Json { value, .. } => {
+            features.push("\"json\"");
+            dependencies.push_str(", serde_json = \"1\"");
+            let json = serde_json::to_string_pretty(value).unwrap_or_default();
+            calls.push(format!(
+                ".json(&serde_json::json!({}))",
+                indent_rest(&json, "    ")
+            ));
+        }
+        Body::Form { pairs, .. } => {
+            let entries: Vec<String> = pairs
+                .iter()
+                .map(|(name, value)| {
+                    format!("        ({}, {}),", rust_quote(name), rust_quote(value))
+                })
+                .collect();
+            calls.push(format!(".form(&[\n{}\n    ])", entries.join("\n")));
+        }
+        Body::Multipart(params) => {
+            features.push("\"multipart\"");
+            let mut form = vec!["let form = reqwest::blocking::multipart::Form::new()".to_string()];
+            for param in params.iter() {
+                form.push(match &param.file_name {
+                    Some(file_name) => format!(
+                        "    .file({}, {})?",
+                        rust_quote(&param.name),
+                        rust_quote(file_name)
+                    ),
+                    None => format!(
+                        "    .text({}, {})",
+                        rust_quote(&param.name),
+                        rust_quote(param.value.as_deref().unwrap_or_default())
+                    ),
+                });
+            }
+            if let Some(last) = form.last_mut() {
+                last.push(';');
+            }
+            statements.push(form.join("\n"));
+            calls.push(".multipart(form)".to_string());
+        }
+        Body::Text(text) => calls.push(format!(".body({})", rust_quote(text))),
+    }
+    calls.push(".send()?;".to_string());
+
+    statements.push(format!("let response = {}", calls.join("\n    ")));
+    statements.push("println!(\"{}\", response.text()?);".to_string());
+    statements.push("Ok(())".to_string());
+
+    format!(
+        "// Cargo.toml: reqwest = {{ version = \"0.12\", features = [{}] }}{}\n\
+         fn main() -> Result<(), Box<dyn std::error::Error>> {{\n{}\n}}",
+        features.join(", "),
+        dependencies,
+        indent_lines(&statements.join("\n"), "    ")
+    )
+}
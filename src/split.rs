@@ -0,0 +1,98 @@
+//! Breaking a HAR file up into smaller ones, e.g. one file per page of a long capture.
+
+use crate::har::Har;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum SplitBy {
+    /// One file per page, and one for the entries without a page
+    Page,
+    /// One file per host name
+    Domain,
+    /// One file per time window, counted from the first request
+    Time,
+}
+
+/// Splits the entries into parts named after their key, in the order of the pages,
+/// of the first request to each domain, or of the time windows.
+/// `window` is the length of a time window in milliseconds; windows without entries are left out.
+/// Every entry ends up in exactly one part.
+pub fn split(har: &Har, by: SplitBy, window: f64) -> Vec<(String, Har)> {
+    // Each key comes with its position among the parts, which is the same for all domains
+    // so they stay in order of appearance. Entries are grouped by both, so a page whose id
+    // happens to be `no-page` is not merged with the entries without a page.
+    let keys: Vec<(usize, String)> = match by {
+        SplitBy::Page => page_keys(har),
+        SplitBy::Domain => har
+            .log
+            .entries
+            .iter()
+            .map(|entry| {
+                let domain = entry.request.url.host_str().unwrap_or("no-domain");
+                (0, domain.to_string())
+            })
+            .collect(),
+        SplitBy::Time => time_keys(har, window),
+    };
+
+    let mut parts: Vec<((usize, String), Vec<usize>)> = Vec::new();
+    let mut positions: HashMap<(usize, String), usize> = HashMap::new();
+    for (i, key) in keys.into_iter().enumerate() {
+        let position = *positions.entry(key.clone()).or_insert_with(|| {
+            parts.push((key, Vec::new()));
+            parts.len() - 1
+        });
+        parts[position].1.push(i);
+    }
+    parts.sort_by_key(|((order, _), _)| *order);
+
+    parts
+        .into_iter()
+        .map(|((_, name), indices)| (name, har.with_entries(&indices)))
+        .collect()
+}
+
+/// Pages come in the order of the file, with the entries without a page last
+fn page_keys(har: &Har) -> Vec<(usize, String)> {
+    let pages = har.log.pages.as_deref().unwrap_or_default();
+    har.log
+        .entries
+        .iter()
+        .map(|entry| {
+            let page = entry
+                .pageref
+                .as_ref()
+                .and_then(|id| pages.iter().position(|page| &page.id == id));
+            match page {
+                Some(position) => (position, pages[position].id.clone()),
+                None => (usize::MAX, "no-page".to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Entries whose start time cannot be parsed come last, in a part of their own
+fn time_keys(har: &Har, window: f64) -> Vec<(usize, String)> {
+    let started: Vec<Option<i64>> = har
+        .log
+        .entries
+        .iter()
+        .map(|entry| {
+            chrono::DateTime::parse_from_rfc3339(&entry.started_date_time)
+                .ok()
+                .map(|time| time.timestamp_millis())
+        })
+        .collect();
+    let first = started.iter().flatten().min().copied().unwrap_or_default();
+
+    started
+        .into_iter()
+        .map(|time| match time {
+            Some(time) => {
+                let window = ((time - first) as f64 / window).floor() as usize;
+                (window, format!("window-{:03}", window + 1))
+            }
+            None => (usize::MAX, "unknown-time".to_string()),
+        })
+        .collect()
+}
@@ -0,0 +1,331 @@
+//! Summary of the entries of a HAR file, to paste into reports.
+
+use crate::body::format_bytes;
+use crate::har::{Entry, Har};
+use crate::list::align_columns;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub requests: usize,
+    /// Sum of `response.bodySize`, the bytes received over the network
+    pub transferred_bytes: i64,
+    /// Sum of `response.content.size`, the size of the decoded bodies
+    pub content_bytes: i64,
+    /// Sum of `onLoad` of the pages the entries belong to, in milliseconds
+    pub page_load_time: Option<f64>,
+    pub status_classes: Vec<Group>,
+    pub methods: Vec<Group>,
+    pub mime_types: Vec<Group>,
+    pub domains: Vec<Group>,
+    pub http_versions: Vec<Group>,
+    pub slowest: Vec<EntrySummary>,
+    pub largest: Vec<EntrySummary>,
+    pub pages: Vec<PageSummary>,
+}
+
+/// Entries sharing the value of a field
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Group {
+    pub name: String,
+    pub requests: usize,
+    pub transferred_bytes: i64,
+    pub content_bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntrySummary {
+    /// Position of the entry in the file, counted from 1
+    pub entry: usize,
+    pub method: String,
+    pub url: String,
+    pub status: i64,
+    pub time: f64,
+    pub size: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageSummary {
+    pub id: String,
+    pub title: String,
+    pub requests: usize,
+    pub on_content_load: Option<f64>,
+    pub on_load: Option<f64>,
+}
+
+/// Sizes are `-1` when unknown, which must not reduce the totals
+fn transferred_bytes(entry: &Entry) -> i64 {
+    entry.response.body_size.max(0)
+}
+
+fn content_bytes(entry: &Entry) -> i64 {
+    entry.response.content.size.unwrap_or(0).max(0)
+}
+
+fn status_class(entry: &Entry) -> String {
+    match entry.response.status {
+        0 => "no response".to_string(),
+        status => format!("{}xx", status / 100),
+    }
+}
+
+fn mime_type(entry: &Entry) -> String {
+    let mime_type = entry.response.content.mime_type.as_deref().unwrap_or("");
+    match mime_type.split(';').next().unwrap_or("").trim() {
+        "" => "(none)".to_string(),
+        essence => essence.to_ascii_lowercase(),
+    }
+}
+
+fn domain(entry: &Entry) -> String {
+    entry.request.url.host_str().unwrap_or("(none)").to_string()
+}
+
+fn http_version(entry: &Entry) -> String {
+    [&entry.response.http_version, &entry.request.http_version]
+        .into_iter()
+        .find(|version| !version.is_empty())
+        .cloned()
+        .unwrap_or_else(|| "(unknown)".to_string())
+}
+
+/// Groups the entries by a key, the largest groups first
+fn group(entries: &[(usize, &Entry)], key: fn(&Entry) -> String) -> Vec<Group> {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (_, entry) in entries {
+        let name = key(entry);
+        let position = *positions.entry(name.clone()).or_insert_with(|| {
+            groups.push(Group {
+                name,
+                requests: 0,
+                transferred_bytes: 0,
+                content_bytes: 0,
+            });
+            groups.len() - 1
+        });
+        let group = &mut groups[position];
+        group.requests += 1;
+        group.transferred_bytes += transferred_bytes(entry);
+        group.content_bytes += content_bytes(entry);
+    }
+
+    // The sort is stable, so groups of the same size stay in order of appearance
+    groups.sort_by_key(|group| std::cmp::Reverse(group.requests));
+    groups
+}
+
+impl EntrySummary {
+    fn new(index: usize, entry: &Entry) -> Self {
+        Self {
+            entry: index + 1,
+            method: entry.request.method.clone(),
+            url: entry.request.url.to_string(),
+            status: entry.response.status,
+            time: entry.time,
+            size: entry.response.content.size,
+        }
+    }
+}
+
+impl Stats {
+    /// Summarizes the entries at the given indices, listing `top` of the slowest and the largest
+    pub fn new(har: &Har, indices: &[usize], top: usize) -> Self {
+        let entries: Vec<(usize, &Entry)> = indices
+            .iter()
+            .filter_map(|i| Some((*i, har.log.entries.get(*i)?)))
+            .collect();
+
+        let mut status_classes = group(&entries, status_class);
+        status_classes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut slowest: Vec<&(usize, &Entry)> = entries
+            .iter()
+            .filter(|(_, entry)| entry.time >= 0.0)
+            .collect();
+        slowest.sort_by(|(_, a), (_, b)| b.time.total_cmp(&a.time));
+        let mut largest: Vec<&(usize, &Entry)> = entries
+            .iter()
+            .filter(|(_, entry)| entry.response.content.size.is_some_and(|size| size >= 0))
+            .collect();
+        largest.sort_by_key(|(_, entry)| std::cmp::Reverse(entry.response.content.size));
+
+        let pages: Vec<PageSummary> = har
+            .log
+            .pages
+            .iter()
+            .flatten()
+            .filter_map(|page| {
+                let requests = entries
+                    .iter()
+                    .filter(|(_, entry)| entry.pageref.as_ref() == Some(&page.id))
+                    .count();
+                (requests > 0).then(|| PageSummary {
+                    id: page.id.clone(),
+                    title: page.title.clone(),
+                    requests,
+                    on_content_load: page
+                        .page_timings
+                        .on_content_load
+                        .filter(|time| *time >= 0.0),
+                    on_load: page.page_timings.on_load.filter(|time| *time >= 0.0),
+                })
+            })
+            .collect();
+        let page_load_time = pages
+            .iter()
+            .filter_map(|page| page.on_load)
+            .reduce(|a, b| a + b);
+
+        Self {
+            requests: entries.len(),
+            transferred_bytes: entries
+                .iter()
+                .map(|(_, entry)| transferred_bytes(entry))
+                .sum(),
+            content_bytes: entries.iter().map(|(_, entry)| content_bytes(entry)).sum(),
+            page_load_time,
+            status_classes,
+            methods: group(&entries, |entry| entry.request.method.clone()),
+            mime_types: group(&entries, mime_type),
+            domains: group(&entries, domain),
+            http_versions: group(&entries, http_version),
+            slowest: slowest
+                .into_iter()
+                .take(top)
+                .map(|(i, entry)| EntrySummary::new(*i, entry))
+                .collect(),
+            largest: largest
+                .into_iter()
+                .take(top)
+                .map(|(i, entry)| EntrySummary::new(*i, entry))
+                .collect(),
+            pages,
+        }
+    }
+
+    /// The report as titled sections of aligned lines
+    pub fn sections(&self) -> Vec<(&'static str, Vec<String>)> {
+        let mut summary = vec![
+            vec!["Requests".to_string(), self.requests.to_string()],
+            vec![
+                "Transferred".to_string(),
+                format_bytes(self.transferred_bytes),
+            ],
+            vec![
+                "Content".to_string(),
+                format!("{} uncompressed", format_bytes(self.content_bytes)),
+            ],
+        ];
+        if !self.pages.is_empty() {
+            summary.push(vec!["Pages".to_string(), self.pages.len().to_string()]);
+        }
+        if let Some(time) = self.page_load_time {
+            summary.push(vec!["Page load time".to_string(), format_ms(time)]);
+        }
+
+        let mut sections = vec![("Summary", align_columns(&summary, &[]))];
+        for (title, groups) in [
+            ("Status classes", &self.status_classes),
+            ("Methods", &self.methods),
+            ("MIME types", &self.mime_types),
+            ("Domains", &self.domains),
+            ("HTTP versions", &self.http_versions),
+        ] {
+            sections.push((title, self.group_lines(groups)));
+        }
+
+        let entry_row = |first: String, entry: &EntrySummary| {
+            vec![
+                first,
+                format!("#{}", entry.entry),
+                entry.status.to_string(),
+                entry.method.clone(),
+                entry.url.clone(),
+            ]
+        };
+        let slowest: Vec<Vec<String>> = self
+            .slowest
+            .iter()
+            .map(|entry| entry_row(format_ms(entry.time), entry))
+            .collect();
+        sections.push(("Slowest requests", align_columns(&slowest, &[0, 1])));
+        let largest: Vec<Vec<String>> = self
+            .largest
+            .iter()
+            .map(|entry| entry_row(format_bytes(entry.size.unwrap_or(0)), entry))
+            .collect();
+        sections.push(("Largest responses", align_columns(&largest, &[0, 1])));
+
+        if !self.pages.is_empty() {
+            let optional_ms = |time: Option<f64>| time.map(format_ms).unwrap_or("---".to_string());
+            let mut pages = vec![vec![
+                "DOMContentLoaded".to_string(),
+                "Load".to_string(),
+                "Requests".to_string(),
+                "Title".to_string(),
+            ]];
+            pages.extend(self.pages.iter().map(|page| {
+                vec![
+                    optional_ms(page.on_content_load),
+                    optional_ms(page.on_load),
+                    page.requests.to_string(),
+                    page.title.clone(),
+                ]
+            }));
+            sections.push(("Pages", align_columns(&pages, &[0, 1, 2])));
+        }
+
+        sections
+    }
+
+    fn group_lines(&self, groups: &[Group]) -> Vec<String> {
+        let header = ["", "Requests", "Share", "Transferred", "Content"].map(String::from);
+        let rows: Vec<Vec<String>> = std::iter::once(header.to_vec())
+            .chain(groups.iter().map(|group| {
+                vec![
+                    group.name.clone(),
+                    group.requests.to_string(),
+                    format!(
+                        "{:.1}%",
+                        group.requests as f64 * 100.0 / self.requests.max(1) as f64
+                    ),
+                    format_bytes(group.transferred_bytes),
+                    format_bytes(group.content_bytes),
+                ]
+            }))
+            .collect();
+        align_columns(&rows, &[1, 2, 3, 4])
+    }
+}
+
+fn format_ms(time: f64) -> String {
+    format!("{:.0} ms", time)
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (title, lines)) in self.sections().into_iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", title)?;
+            for line in lines {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
-use crate::app::App;
 use crate::event::EventHandler;
-use crate::ui;
 use anyhow::Context;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::Backend;
-use ratatui::Terminal;
+use ratatui::{Frame, Terminal};
 use std::io;
 use std::panic;
 
@@ -22,7 +22,12 @@ impl<B: Backend> Tui<B> {
 
     pub fn init(&mut self) -> anyhow::Result<()> {
         terminal::enable_raw_mode()?;
-        crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+        crossterm::execute!(
+            io::stderr(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
 
         let panic_hook = panic::take_hook();
 
@@ -40,15 +45,20 @@ impl<B: Backend> Tui<B> {
         Ok(())
     }
 
-    pub fn draw(&mut self, app: &mut App) -> anyhow::Result<()> {
-        self.terminal.draw(|frame| ui::render(app, frame))?;
+    pub fn draw(&mut self, render: impl FnOnce(&mut Frame)) -> anyhow::Result<()> {
+        self.terminal.draw(render)?;
         Ok(())
     }
 
     fn reset() -> anyhow::Result<()> {
         terminal::disable_raw_mode().context("failed to disable raw mode")?;
-        crossterm::execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture)
-            .context("failed to leave alternate screen and disable mouse capture")?;
+        crossterm::execute!(
+            io::stderr(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableBracketedPaste
+        )
+        .context("failed to leave alternate screen and disable mouse capture")?;
 
         Ok(())
     }
@@ -59,3 +69,15 @@ impl<B: Backend> Tui<B> {
         Ok(())
     }
 }
+
+/// Copies the text to the system clipboard with the OSC 52 escape sequence, which the
+/// terminal emulator handles, so it also works over SSH and in tmux with `set-clipboard on`
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    use base64::Engine;
+    use std::io::Write;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stderr = io::stderr();
+    write!(stderr, "\x1b]52;c;{}\x07", encoded)?;
+    stderr.flush()
+}
@@ -1,78 +1,374 @@
-use crate::app::{App, HeaderInfo, TabBarState, TableItem};
+use crate::app::{
+    self, App, ComparedField, ConnectionInfo, CookieInfo, DiffApp, HeaderInfo, HeaderSection,
+    MainView, PageEvent, Pane, PreviewWidgetState, RequestBody, RequestInfo, ResponseBody,
+    ResponseInfo, ScreenLayout, Search, SortColumn, Summary, TabBarState, TableItem, TimingInfo,
+    TimingPhase, Waterfall, HEADER_SECTIONS, TABBAR_ITEMS, TABLES_ROWS_COUNT,
+};
+use crate::body;
+use crate::detect::{self, Finding};
+use crate::diff::{self, Change, EntryDiff};
+use crate::har;
+use crate::lenient::Warning;
+use crate::snippet;
 use ratatui::{prelude::*, widgets::*};
 
 pub fn render(app: &mut App, frame: &mut Frame) {
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Fill(1), Constraint::Fill(1)])
+        .constraints([Constraint::Fill(1), Constraint::Length(1)])
         .split(frame.size());
 
-    render_table(app, main_layout[0], frame.buffer_mut());
-    render_preview(app, main_layout[1], frame.buffer_mut());
+    app.screen = ScreenLayout {
+        main: main_layout[0],
+        ..Default::default()
+    };
+    let preview_size = app.preview_size;
+    let split = |direction: Direction| {
+        Layout::default()
+            .direction(direction)
+            .constraints([
+                Constraint::Percentage(100 - preview_size),
+                Constraint::Percentage(preview_size),
+            ])
+            .split(main_layout[0])
+    };
+
+    match app.preview_widget_state {
+        PreviewWidgetState::Hidden => render_table(app, main_layout[0], frame.buffer_mut()),
+        PreviewWidgetState::Full => render_preview(app, main_layout[0], frame.buffer_mut()),
+        PreviewWidgetState::Bottom => {
+            let layout = split(Direction::Vertical);
+            render_table(app, layout[0], frame.buffer_mut());
+            render_preview(app, layout[1], frame.buffer_mut());
+        }
+        PreviewWidgetState::Right => {
+            let layout = split(Direction::Horizontal);
+            render_table(app, layout[0], frame.buffer_mut());
+            render_preview(app, layout[1], frame.buffer_mut());
+        }
+    }
+    render_status_bar(app, main_layout[1], frame.buffer_mut());
+
+    if let Some(scroll) = app.warnings_panel {
+        let panel = WarningsPanel::init(app, scroll);
+        panel.render(main_layout[0], frame.buffer_mut());
+    }
+    if let Some(summary) = &app.summary {
+        let panel = SummaryPanel::init(summary);
+        panel.render(main_layout[0], frame.buffer_mut());
+    }
+    if let Some(position) = app.snippet_picker {
+        let picker = SnippetPicker::init(app, position);
+        picker.render(main_layout[0], frame.buffer_mut());
+    }
 }
 
 pub fn render_table(app: &mut App, area: Rect, buf: &mut Buffer) {
-    let table = EntriesTable::init(&app);
-    let mut state = TableState::default();
-    state.select(Some(app.get_index()));
-    table.render(area, buf, &mut state);
+    // Borders and the header row are not part of a page
+    let rows_height = area.height.saturating_sub(3);
+    app.set_table_page_size(rows_height as usize);
+    // Both views draw the rows below a border and a header line
+    let table_rows = Rect {
+        x: area.x + 1,
+        y: area.y + 2,
+        width: area.width.saturating_sub(2),
+        height: rows_height,
+    };
+
+    let table_offset = match app.main_view {
+        MainView::Table => {
+            let table = EntriesTable::init(app);
+            let mut state = TableState::default();
+            state.select(Some(app.get_index()));
+            table.render(area, buf, &mut state);
+            state.offset()
+        }
+        MainView::Waterfall => {
+            let waterfall = WaterfallWidget::init(app);
+            let offset = waterfall.row_offset(area);
+            waterfall.render(area, buf);
+            offset
+        }
+    };
+
+    app.screen.table = area;
+    app.screen.table_rows = table_rows;
+    app.screen.table_offset = table_offset;
 }
 
 pub fn render_preview(app: &mut App, area: Rect, buf: &mut Buffer) {
+    app.sync_preview_scroll();
     let preview = PreviewWidget::init(app);
-    preview.render(area, buf);
+    let mut state = PreviewScroll {
+        offset: app.preview_scroll(),
+        ..Default::default()
+    };
+    preview.render(area, buf, &mut state);
+    app.set_preview_viewport(state.page_size, state.content_height);
+
+    app.screen.preview = area;
+    app.screen.tabbar_row = area.y;
+    app.screen.tabs = tab_ranges(area);
+}
+
+/// Returns the columns each tab spans on the tab bar, matching how `Tabs` lays them out
+fn tab_ranges(area: Rect) -> Vec<(TabBarState, u16, u16)> {
+    let mut x = area.x;
+    TABBAR_ITEMS
+        .iter()
+        .map(|item| {
+            // A padding on both sides, followed by a divider
+            let start = x;
+            let end = start + item.to_string().chars().count() as u16 + 2;
+            x = end + 1;
+            (item.clone(), start, end)
+        })
+        .collect()
+}
+
+pub fn render_status_bar(app: &mut App, area: Rect, buf: &mut Buffer) {
+    let status_bar = StatusBar::init(app);
+    status_bar.render(area, buf);
+}
+
+#[derive(Debug)]
+struct StatusBar {
+    prompt: Option<(String, String, Option<String>)>,
+    search: Option<(String, Option<usize>, usize)>,
+    filter: Option<String>,
+    warnings: usize,
+    marked: usize,
+    sensitive: usize,
+    message: Option<String>,
+    position: (usize, usize, usize),
+}
+
+impl StatusBar {
+    pub fn init(app: &App) -> Self {
+        Self {
+            prompt: app.prompt.as_ref().map(|prompt| {
+                (
+                    prompt.kind.prefix().to_string(),
+                    prompt.input.clone(),
+                    prompt.error.clone(),
+                )
+            }),
+            search: app.search.as_ref().and_then(|search| {
+                let (position, count) = app.search_position()?;
+                Some((search.input.clone(), position, count))
+            }),
+            filter: app.filter.as_ref().map(|filter| filter.input.clone()),
+            warnings: app.warnings.len(),
+            marked: app.marked_count(),
+            sensitive: app.sensitive_count(),
+            message: app.message.clone(),
+            position: (
+                (app.get_index() + 1).min(app.max_index()),
+                app.max_index(),
+                app.har.log.entries.len(),
+            ),
+        }
+    }
+}
+
+impl Widget for StatusBar {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        if let Some((prefix, input, error)) = self.prompt {
+            let mut spans = vec![
+                Span::styled(prefix, Style::default().bold()),
+                Span::raw(input),
+                Span::styled(" ", Style::default().reversed()),
+            ];
+            if let Some(error) = error {
+                spans.push(Span::styled(
+                    format!("  {}", error),
+                    Style::default().fg(Color::LightRed),
+                ));
+            }
+            let line = Line::from(spans);
+            Widget::render(Paragraph::new(line), area, buf);
+            return;
+        }
+
+        let mut left = Vec::new();
+        if let Some(message) = self.message {
+            left.push(Span::styled(message, Style::default().fg(Color::LightRed)));
+        } else if let Some((input, position, count)) = self.search {
+            left.push(Span::styled("/", Style::default().bold()));
+            left.push(Span::raw(input));
+            left.push(Span::styled(
+                match position {
+                    Some(position) => format!("  [{}/{}]", position, count),
+                    None => format!("  [{} matches]", count),
+                },
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        let (position, visible, total) = self.position;
+        let mut right = Vec::new();
+        if self.marked > 0 {
+            right.push(Span::styled(
+                format!("{} marked  ", self.marked),
+                Style::default().fg(Color::LightGreen),
+            ));
+        }
+        if self.sensitive > 0 {
+            right.push(Span::styled(
+                format!("{} sensitive  ", self.sensitive),
+                Style::default().fg(Color::LightMagenta),
+            ));
+        }
+        if self.warnings > 0 {
+            right.push(Span::styled(
+                format!("{} warnings  ", self.warnings),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if let Some(filter) = self.filter {
+            right.push(Span::styled(
+                format!("filter: {}  ", filter),
+                Style::default().fg(Color::LightCyan),
+            ));
+        }
+        right.push(Span::styled(
+            if visible == total {
+                format!("{}/{} ", position, visible)
+            } else {
+                format!("{}/{} (of {}) ", position, visible, total)
+            },
+            Style::default().fg(Color::DarkGray),
+        ));
+        let right = Line::from(right);
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(right.width() as u16),
+            ])
+            .split(area);
+
+        Widget::render(Paragraph::new(Line::from(left)), layout[0], buf);
+        Widget::render(Paragraph::new(right), layout[1], buf);
+    }
+}
+
+/// The table is only marked as focused while the preview is shown next to it
+fn table_focused(app: &App) -> bool {
+    app.focused_pane() == Pane::Table && app.preview_widget_state != PreviewWidgetState::Hidden
+}
+
+/// The area of a panel shown over the others, centered and leaving a margin around it
+fn centered_rect(area: Rect) -> Rect {
+    Rect {
+        x: area.x + area.width / 10,
+        y: area.y + area.height / 10,
+        width: area.width - area.width / 5,
+        height: area.height - area.height / 5,
+    }
+}
+
+/// The lines from `offset` on, since `Paragraph::scroll` wraps offsets past `u16::MAX`
+fn scrolled_paragraph(lines: Vec<Line<'_>>, offset: usize) -> Paragraph<'_> {
+    Paragraph::new(lines.into_iter().skip(offset).collect::<Vec<Line>>())
+}
+
+fn border_style(focused: bool) -> Style {
+    if focused {
+        Style::default().fg(Color::LightCyan)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    }
 }
 
 #[derive(Debug)]
 pub struct EntriesTable {
     table_items: Vec<TableItem>,
+    focused: bool,
+    search: Option<Search>,
+    sort_column: Option<SortColumn>,
+    sort_descending: bool,
 }
 
-impl<'a> EntriesTable {
+impl EntriesTable {
     pub fn init(app: &App) -> Self {
-        let mut state = TableState::default();
-        let index = app.get_index();
-        state.select(Some(index));
-
         Self {
-            table_items: app.har.to_table_items(),
+            table_items: app.table_items(),
+            focused: table_focused(app),
+            search: app.search.clone(),
+            sort_column: app.sort_column,
+            sort_descending: app.sort_descending,
         }
     }
 
-    fn table(&self) -> Table {
+    fn sort_indicator(&self) -> &'static str {
+        if self.sort_descending {
+            "▼"
+        } else {
+            "▲"
+        }
+    }
+
+    fn header_cell(&self, column: SortColumn, label: &str) -> Cell<'static> {
+        if self.sort_column == Some(column) {
+            Cell::from(Line::from(vec![
+                Span::raw(label.to_string()),
+                Span::styled(self.sort_indicator(), Style::default().fg(Color::Yellow)),
+            ]))
+        } else {
+            Cell::from(label.to_string())
+        }
+    }
+
+    fn table(&self) -> Table<'_> {
         let headers = Row::new(vec![
-            Cell::from("Status"),
-            Cell::from("Method"),
-            Cell::from("Domain"),
-            Cell::from("FileName"),
-            Cell::from("ContentType"),
-            Cell::from("     Size  "),
+            Cell::from("   "),
+            self.header_cell(SortColumn::Status, "Status"),
+            self.header_cell(SortColumn::Method, "Method"),
+            self.header_cell(SortColumn::Domain, "Domain"),
+            self.header_cell(SortColumn::FileName, "FileName"),
+            self.header_cell(SortColumn::ContentType, "ContentType"),
+            self.header_cell(SortColumn::Size, "     Size  "),
+            self.header_cell(SortColumn::Time, "     Time"),
         ])
         .style(Style::default().bold().underlined());
 
-        let widths = [
-            Constraint::Length(6),
-            Constraint::Length(6),
+        // Columns that are not shown in the table are noted in the title instead
+        let title = match self.sort_column {
+            Some(SortColumn::Started) => format!(" Sorted by Started {} ", self.sort_indicator()),
+            None if self.sort_descending => " Reversed ".to_string(),
+            _ => String::new(),
+        };
+
+        let widths: [Constraint; TABLES_ROWS_COUNT] = [
+            Constraint::Length(3),
+            Constraint::Length(7),
+            Constraint::Length(7),
             Constraint::Fill(1),
             Constraint::Fill(2),
             Constraint::Length(12),
             Constraint::Length(12),
+            Constraint::Length(10),
         ];
 
         let rows: Vec<Row> = self
             .table_items
             .iter()
-            .map(|item| item.to_table_row())
+            .map(|item| item.to_table_row(self.search.as_ref()))
             .collect();
 
-        Table::new(rows, &widths)
+        Table::new(rows, widths)
             .header(headers)
             .highlight_style(Style::default().reversed())
             .block(
                 Block::default()
+                    .title(title)
                     .padding(Padding::horizontal(1))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::DarkGray)),
+                    .border_style(border_style(self.focused)),
             )
     }
 }
@@ -87,35 +383,432 @@ impl StatefulWidget for EntriesTable {
     }
 }
 
+#[derive(Debug)]
+struct WaterfallWidget {
+    table_items: Vec<TableItem>,
+    waterfall: Waterfall,
+    selected: usize,
+    focused: bool,
+}
+
+impl WaterfallWidget {
+    const LABEL_WIDTH: usize = 40;
+
+    pub fn init(app: &App) -> Self {
+        Self {
+            table_items: app.table_items(),
+            waterfall: app.har.to_waterfall(),
+            selected: app.get_index(),
+            focused: table_focused(app),
+        }
+    }
+
+    /// Returns the position of the entry on the first row, keeping the selection visible
+    fn row_offset(&self, area: Rect) -> usize {
+        // Borders, the axis and the legend take four lines
+        let visible_rows = (area.height as usize).saturating_sub(4).max(1);
+        self.selected.saturating_sub(visible_rows - 1)
+    }
+
+    fn marker_color(event: PageEvent) -> Color {
+        match event {
+            PageEvent::ContentLoad => Color::LightBlue,
+            PageEvent::Load => Color::LightRed,
+        }
+    }
+
+    fn axis_line(&self, bar_width: usize, end: f64) -> Line<'static> {
+        let mut axis = vec![' '; bar_width];
+        for i in 0..=4 {
+            let label = format_millis(end * i as f64 / 4.0);
+            let column =
+                (bar_width.saturating_sub(1) * i / 4).min(bar_width.saturating_sub(label.len()));
+            for (j, c) in label.chars().enumerate() {
+                if let Some(cell) = axis.get_mut(column + j) {
+                    *cell = c;
+                }
+            }
+        }
+
+        Line::from(vec![
+            Span::raw(format!("{:<width$}", "", width = Self::LABEL_WIDTH + 1)),
+            Span::styled(
+                axis.into_iter().collect::<String>(),
+                Style::default().bold(),
+            ),
+        ])
+    }
+
+    fn legend_line(&self) -> Line<'static> {
+        let mut spans = Vec::new();
+        for (event, millis) in &self.waterfall.markers {
+            spans.push(Span::styled(
+                "│ ",
+                Style::default().fg(Self::marker_color(*event)),
+            ));
+            spans.push(Span::raw(format!("{} {}  ", event, format_millis(*millis))));
+        }
+
+        Line::from(spans)
+    }
+
+    fn bar_spans(&self, index: usize, bar_width: usize, end: f64) -> Vec<Span<'static>> {
+        let column = |millis: f64| ((millis / end) * bar_width as f64) as usize;
+        let mut cells: Vec<(char, Style)> = vec![(' ', Style::default()); bar_width];
+
+        for (event, millis) in &self.waterfall.markers {
+            if let Some(cell) = cells.get_mut(column(*millis).min(bar_width.saturating_sub(1))) {
+                *cell = ('│', Style::default().fg(Self::marker_color(*event)));
+            }
+        }
+
+        if let Some(item) = self.waterfall.items.get(index) {
+            let mut elapsed = item.offset;
+            for (phase, duration) in &item.phases {
+                let start = column(elapsed);
+                let stop = column(elapsed + duration).max(start + 1);
+                for cell in cells.iter_mut().take(stop).skip(start) {
+                    *cell = ('█', Style::default().fg(timing_phase_color(*phase)));
+                }
+                elapsed += duration;
+            }
+            if item.phases.is_empty() {
+                if let Some(cell) = cells.get_mut(column(item.offset)) {
+                    *cell = ('▏', Style::default().fg(Color::DarkGray));
+                }
+            }
+        }
+
+        cells
+            .into_iter()
+            .map(|(c, style)| Span::styled(c.to_string(), style))
+            .collect()
+    }
+}
+
+impl Widget for WaterfallWidget {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let block = Block::default()
+            .padding(Padding::horizontal(1))
+            .borders(Borders::ALL)
+            .border_style(border_style(self.focused));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let bar_width = (inner.width as usize)
+            .saturating_sub(Self::LABEL_WIDTH + 1)
+            .max(10);
+        let end = self.waterfall.end().max(1.0);
+        let visible_rows = (inner.height as usize).saturating_sub(2).max(1);
+        let offset = self.row_offset(area);
+
+        let mut lines = vec![self.axis_line(bar_width, end)];
+        for (i, item) in self
+            .table_items
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible_rows)
+        {
+            let label: String = item
+                .to_label_spans()
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect();
+            let mut label_spans = item.to_label_spans();
+            if label.chars().count() > Self::LABEL_WIDTH {
+                label_spans = vec![Span::raw(truncate(&label, Self::LABEL_WIDTH))];
+            } else {
+                label_spans.push(Span::raw(
+                    " ".repeat(Self::LABEL_WIDTH - label.chars().count()),
+                ));
+            }
+            if i == self.selected {
+                label_spans = label_spans
+                    .into_iter()
+                    .map(|span| span.patch_style(Style::default().reversed()))
+                    .collect();
+            }
+
+            let mut spans = label_spans;
+            spans.push(Span::raw(" "));
+            spans.extend(self.bar_spans(item.get_index(), bar_width, end));
+            lines.push(Line::from(spans));
+        }
+        lines.push(self.legend_line());
+
+        Widget::render(Paragraph::new(lines), inner, buf);
+    }
+}
+
+#[derive(Debug)]
+struct WarningsPanel {
+    warnings: Vec<Warning>,
+    scroll: usize,
+}
+
+impl WarningsPanel {
+    pub fn init(app: &App, scroll: usize) -> Self {
+        Self {
+            warnings: app.warnings.clone(),
+            scroll,
+        }
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        if self.warnings.is_empty() {
+            return vec![none_line()];
+        }
+
+        self.warnings
+            .iter()
+            .flat_map(|warning| {
+                let mut lines = vec![Line::from(Span::styled(
+                    warning.path.clone(),
+                    Style::default().fg(Color::Yellow),
+                ))];
+                lines.extend(text_lines(&warning.message, width));
+                lines
+            })
+            .collect()
+    }
+}
+
+impl Widget for WarningsPanel {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = centered_rect(area);
+        let block = Block::default()
+            .title(format!(" Warnings ({}) ", self.warnings.len()))
+            .title_bottom(" j/k: scroll  W/Esc: close ")
+            .padding(Padding::horizontal(1))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        let lines = self.lines(inner.width);
+        Widget::render(scrolled_paragraph(lines, self.scroll), inner, buf);
+    }
+}
+
+#[derive(Debug)]
+struct SummaryPanel {
+    requests: usize,
+    sections: Vec<(&'static str, Vec<String>)>,
+    scroll: usize,
+}
+
+impl SummaryPanel {
+    pub fn init(summary: &Summary) -> Self {
+        Self {
+            requests: summary.stats.requests,
+            sections: summary.stats.sections(),
+            scroll: summary.scroll,
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (title, section_lines) in &self.sections {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(
+                title.to_string(),
+                Style::default().fg(Color::LightCyan).bold(),
+            )));
+            lines.extend(
+                section_lines
+                    .iter()
+                    .map(|line| Line::from(format!("  {}", line))),
+            );
+        }
+        lines
+    }
+}
+
+impl Widget for SummaryPanel {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = centered_rect(area);
+        let block = Block::default()
+            .title(format!(" Summary of {} entries ", self.requests))
+            .title_bottom(" j/k: scroll  i/Esc: close ")
+            .padding(Padding::horizontal(1))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightCyan));
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+        Widget::render(scrolled_paragraph(self.lines(), self.scroll), inner, buf);
+    }
+}
+
+#[derive(Debug)]
+struct SnippetPicker {
+    position: usize,
+    snippet: Option<String>,
+}
+
+impl SnippetPicker {
+    pub fn init(app: &App, position: usize) -> Self {
+        Self {
+            position,
+            snippet: app.selected_snippet(snippet::FORMATS[position]),
+        }
+    }
+
+    fn list(&self) -> List<'static> {
+        List::new(
+            snippet::FORMATS
+                .iter()
+                .map(|format| ListItem::new(format.to_string())),
+        )
+        .highlight_style(Style::default().fg(Color::LightCyan).bold().reversed())
+    }
+}
+
+impl Widget for SnippetPicker {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let area = centered_rect(area);
+        let block = Block::default()
+            .title(" Copy as ")
+            .title_bottom(" j/k: select  Enter: copy  Esc: close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightCyan));
+        let inner = block.inner(area);
+
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(20), Constraint::Fill(1)])
+            .split(inner);
+        let mut state = ListState::default().with_selected(Some(self.position));
+        StatefulWidget::render(
+            self.list().block(
+                Block::default()
+                    .borders(Borders::RIGHT)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            ),
+            layout[0],
+            buf,
+            &mut state,
+        );
+
+        let snippet_area = Rect {
+            x: layout[1].x + 1,
+            width: layout[1].width.saturating_sub(1),
+            ..layout[1]
+        };
+        let lines: Vec<Line> = self
+            .snippet
+            .unwrap_or_default()
+            .lines()
+            .map(|line| Line::from(line.replace('\t', "    ")))
+            .collect();
+        Widget::render(Paragraph::new(lines), snippet_area, buf);
+    }
+}
+
+#[derive(Debug)]
+enum PreviewContent {
+    Headers(Box<HeaderPreview>),
+    Cookies(CookiePreview),
+    Request(RequestPreview),
+    Response(ResponsePreview),
+    Timing(TimingPreview),
+    Security(SecurityPreview),
+}
+
+impl PreviewContent {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match self {
+            Self::Headers(preview) => preview.to_lines(width),
+            Self::Cookies(preview) => preview.to_lines(width),
+            Self::Request(preview) => preview.to_lines(width),
+            Self::Response(preview) => preview.to_lines(width),
+            Self::Timing(preview) => preview.to_lines(width),
+            Self::Security(preview) => preview.to_lines(width),
+        }
+    }
+}
+
+/// A preview tab renders its content as lines, which the preview widget scrolls
+trait PreviewTab {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>>;
+}
+
+#[derive(Debug, Default)]
+pub struct PreviewScroll {
+    pub offset: usize,
+    pub page_size: usize,
+    pub content_height: usize,
+}
+
 #[derive(Debug)]
 struct PreviewWidget {
     tabbar_state: TabBarState,
+    content: Option<PreviewContent>,
+    focused: bool,
 }
 
 impl PreviewWidget {
     pub fn init(app: &App) -> Self {
+        let content = match app.tabbar_state {
+            TabBarState::Headers => {
+                Some(PreviewContent::Headers(Box::new(HeaderPreview::init(app))))
+            }
+            TabBarState::Cookies => Some(PreviewContent::Cookies(CookiePreview::init(app))),
+            TabBarState::Request => Some(PreviewContent::Request(RequestPreview::init(app))),
+            TabBarState::Response => Some(PreviewContent::Response(ResponsePreview::init(app))),
+            TabBarState::Timing => Some(PreviewContent::Timing(TimingPreview::init(app))),
+            TabBarState::Security => Some(PreviewContent::Security(SecurityPreview::init(app))),
+        };
+
         Self {
             tabbar_state: app.tabbar_state.clone(),
+            content,
+            focused: app.focused_pane() == Pane::Preview
+                && app.preview_widget_state != PreviewWidgetState::Full,
         }
     }
 
-    fn tabbar(&self) -> Tabs {
-        Tabs::new(vec![
-            " [1] Headers ",
-            " [2] Cookies ",
-            " [3] Request ",
-            " [4] Response ",
-        ])
+    fn tabbar(&self) -> Tabs<'_> {
+        Tabs::new(
+            TABBAR_ITEMS
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<String>>(),
+        )
         .select(self.tabbar_state.to_index())
         .padding(" ", " ")
+        .highlight_style(if self.focused {
+            Style::default().fg(Color::LightCyan).bold().reversed()
+        } else {
+            Style::default().reversed()
+        })
     }
 }
 
-impl Widget for PreviewWidget {
-    fn render(self, area: Rect, buf: &mut Buffer)
-    where
-        Self: Sized,
-    {
+impl StatefulWidget for PreviewWidget {
+    type State = PreviewScroll;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let tabbar = self.tabbar();
 
         let layout = Layout::default()
@@ -124,29 +817,1333 @@ impl Widget for PreviewWidget {
             .split(area);
 
         Widget::render(tabbar, layout[0], buf);
+
+        // Leave the rightmost column for the scrollbar
+        let content_area = Rect {
+            width: layout[1].width.saturating_sub(1),
+            ..layout[1]
+        };
+        let lines = self
+            .content
+            .as_ref()
+            .map(|content| content.to_lines(content_area.width))
+            .unwrap_or_default();
+
+        state.page_size = content_area.height as usize;
+        state.content_height = lines.len();
+        state.offset = state
+            .offset
+            .min(state.content_height.saturating_sub(state.page_size));
+
+        Widget::render(scrolled_paragraph(lines, state.offset), content_area, buf);
+
+        if state.content_height > state.page_size {
+            let mut scrollbar_state =
+                ScrollbarState::new(state.content_height.saturating_sub(state.page_size))
+                    .position(state.offset)
+                    .viewport_content_length(state.page_size);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(Color::DarkGray))
+                .render(layout[1], buf, &mut scrollbar_state);
+        }
     }
 }
 
 #[derive(Debug)]
 struct HeaderPreview {
     header_info: Option<HeaderInfo>,
+    selected: HeaderSection,
+    collapsed: Vec<HeaderSection>,
 }
 
 impl HeaderPreview {
     pub fn init(app: &App) -> Self {
         Self {
-            header_info: app.har.to_header_info(app.get_index()),
+            header_info: app
+                .selected_entry_index()
+                .and_then(|index| app.har.to_header_info(index)),
+            selected: app.header_section,
+            collapsed: HEADER_SECTIONS
+                .iter()
+                .filter(|section| app.is_header_section_collapsed(section))
+                .copied()
+                .collect(),
         }
     }
+
+    fn section_title(&self, section: HeaderSection, count: Option<usize>) -> Line<'static> {
+        let marker = if self.collapsed.contains(&section) {
+            "▶"
+        } else {
+            "▼"
+        };
+        let title = match count {
+            Some(count) => format!("{} {} ({})", marker, section, count),
+            None => format!("{} {}", marker, section),
+        };
+        let style = if section == self.selected {
+            Style::default().bold().reversed()
+        } else {
+            Style::default().bold()
+        };
+
+        Line::from(Span::styled(title, style))
+    }
+
+    fn lines(&self, header_info: &HeaderInfo, width: u16) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+
+        for section in HEADER_SECTIONS {
+            let pairs = match section {
+                HeaderSection::General | HeaderSection::Sensitive | HeaderSection::Cache => None,
+                HeaderSection::QueryParams => Some(&header_info.query_params),
+                HeaderSection::RequestHeaders => Some(&header_info.req_headers),
+                HeaderSection::ResponseHeaders => Some(&header_info.resp_headers),
+                HeaderSection::Comments => Some(&header_info.comments),
+            };
+
+            let count = match section {
+                HeaderSection::Sensitive => Some(header_info.findings.len()),
+                _ => pairs.map(|pairs| pairs.len()),
+            };
+            lines.push(self.section_title(section, count));
+            if self.collapsed.contains(&section) {
+                continue;
+            }
+
+            match pairs {
+                None if section == HeaderSection::Cache => {
+                    lines.extend(cache_lines(&header_info.cache, width))
+                }
+                None if section == HeaderSection::Sensitive => {
+                    lines.extend(finding_lines(&header_info.findings))
+                }
+                None => lines.extend(general_lines(header_info, width)),
+                Some(pairs) if pairs.is_empty() => lines.push(none_line()),
+                Some(pairs) => lines.extend(key_value_lines(pairs, width)),
+            }
+        }
+
+        lines
+    }
+}
+
+impl PreviewTab for HeaderPreview {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match &self.header_info {
+            Some(header_info) => self.lines(header_info, width),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CookiePreview {
+    cookie_info: Option<CookieInfo>,
+}
+
+impl CookiePreview {
+    pub fn init(app: &App) -> Self {
+        Self {
+            cookie_info: app
+                .selected_entry_index()
+                .and_then(|index| app.har.to_cookie_info(index)),
+        }
+    }
+
+    fn cookie_rows(cookies: &[har::Cookie], with_issues: bool) -> Vec<Vec<Span<'static>>> {
+        cookies
+            .iter()
+            .map(|cookie| {
+                let flag = |value: Option<bool>| match value {
+                    Some(true) => Span::styled("✓", Style::default().fg(Color::LightGreen)),
+                    _ => Span::raw(""),
+                };
+
+                let mut row = vec![
+                    Span::styled(cookie.name.clone(), Style::default().fg(Color::LightCyan)),
+                    if !cookie.value.is_empty()
+                        && (detect::is_session_cookie(&cookie.name)
+                            || !detect::find_ranges(&cookie.value).is_empty())
+                    {
+                        Span::styled(cookie.value.clone(), sensitive_style())
+                    } else {
+                        Span::raw(cookie.value.clone())
+                    },
+                    Span::raw(cookie.domain.clone().unwrap_or_default()),
+                    Span::raw(cookie.path.clone().unwrap_or_default()),
+                    match &cookie.expires {
+                        Some(expires) => Span::raw(format_expires(expires)),
+                        None if with_issues => {
+                            Span::styled("Session", Style::default().fg(Color::DarkGray))
+                        }
+                        None => Span::raw(""),
+                    },
+                    flag(cookie.http_only),
+                    flag(cookie.secure),
+                    Span::raw(cookie.same_site.clone().unwrap_or_default()),
+                    Span::raw(cookie.comment.clone().unwrap_or_default()),
+                ];
+
+                if with_issues {
+                    let issues = app::cookie_issues(cookie)
+                        .iter()
+                        .map(|issue| issue.to_string())
+                        .collect::<Vec<String>>();
+                    row.push(if issues.is_empty() {
+                        Span::raw("")
+                    } else {
+                        Span::styled(
+                            format!("⚠ {}", issues.join(", ")),
+                            Style::default().fg(Color::LightYellow),
+                        )
+                    });
+                }
+
+                row
+            })
+            .collect()
+    }
+
+    fn lines(cookie_info: &CookieInfo) -> Vec<Line<'static>> {
+        let columns = [
+            "Name", "Value", "Domain", "Path", "Expires", "HttpOnly", "Secure", "SameSite",
+            "Comment",
+        ];
+        let mut lines = Vec::new();
+
+        lines.push(title_line(format!(
+            "Request Cookies ({})",
+            cookie_info.req_cookies.len()
+        )));
+        if cookie_info.req_cookies.is_empty() {
+            lines.push(none_line());
+        } else {
+            lines.extend(table_lines(
+                &columns,
+                Self::cookie_rows(&cookie_info.req_cookies, false),
+            ));
+        }
+
+        lines.push(Line::default());
+
+        lines.push(title_line(format!(
+            "Response Cookies ({})",
+            cookie_info.resp_cookies.len()
+        )));
+        if cookie_info.resp_cookies.is_empty() {
+            lines.push(none_line());
+        } else {
+            let mut columns = columns.to_vec();
+            columns.push("Issues");
+            lines.extend(table_lines(
+                &columns,
+                Self::cookie_rows(&cookie_info.resp_cookies, true),
+            ));
+        }
+
+        lines
+    }
+}
+
+impl PreviewTab for CookiePreview {
+    fn to_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        match &self.cookie_info {
+            Some(cookie_info) => Self::lines(cookie_info),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RequestPreview {
+    request_info: Option<RequestInfo>,
 }
 
-impl Widget for HeaderPreview {
+impl RequestPreview {
+    pub fn init(app: &App) -> Self {
+        Self {
+            request_info: app
+                .selected_entry_index()
+                .and_then(|index| app.har.to_request_info(index)),
+        }
+    }
+
+    fn lines(request_info: &RequestInfo, width: u16) -> Vec<Line<'static>> {
+        let mut lines = key_value_lines(
+            &[
+                ("Content-Type".to_string(), request_info.mime_type.clone()),
+                (
+                    "Body Size".to_string(),
+                    match request_info.body_size {
+                        size if size < 0 => "---".to_string(),
+                        size => body::format_bytes(size),
+                    },
+                ),
+            ],
+            width,
+        );
+        lines.push(Line::default());
+
+        match &request_info.body {
+            RequestBody::Empty => {}
+            RequestBody::Json(text) => {
+                lines.push(title_line("Body (JSON)".to_string()));
+                lines.extend(text_lines(text, width));
+            }
+            RequestBody::Form(pairs) => {
+                lines.push(title_line(format!("Form Data ({})", pairs.len())));
+                lines.extend(key_value_lines(pairs, width));
+            }
+            RequestBody::Multipart(parts) => {
+                lines.push(title_line(format!("Multipart Form Data ({})", parts.len())));
+                for (i, part) in parts.iter().enumerate() {
+                    let mut title = format!("Part {}", i + 1);
+                    if let Some(name) = &part.name {
+                        title.push_str(&format!(": {}", name));
+                    }
+                    if let Some(file_name) = &part.file_name {
+                        title.push_str(&format!(" (file: {})", file_name));
+                    }
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", title),
+                        Style::default().fg(Color::LightCyan).bold(),
+                    )));
+                    lines.extend(key_value_lines(&part.headers, width));
+                    lines.extend(text_lines(&part.body, width));
+                }
+            }
+            RequestBody::Text(text) => {
+                lines.push(title_line("Body".to_string()));
+                lines.extend(text_lines(text, width));
+            }
+        }
+
+        let decoded_form = matches!(request_info.body, RequestBody::Form(_));
+        if !request_info.params.is_empty() && !decoded_form {
+            lines.push(Line::default());
+            lines.push(title_line(format!(
+                "Params ({})",
+                request_info.params.len()
+            )));
+            lines.extend(table_lines(
+                &["Name", "Value", "FileName", "ContentType"],
+                request_info
+                    .params
+                    .iter()
+                    .map(|param| {
+                        vec![
+                            Span::styled(param.name.clone(), Style::default().fg(Color::LightCyan)),
+                            Span::raw(param.value.clone().unwrap_or_default()),
+                            Span::raw(param.file_name.clone().unwrap_or_default()),
+                            Span::raw(param.content_type.clone().unwrap_or_default()),
+                        ]
+                    })
+                    .collect(),
+            ));
+        }
+
+        lines
+    }
+}
+
+impl PreviewTab for RequestPreview {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match &self.request_info {
+            Some(request_info) => Self::lines(request_info, width),
+            None => vec![Line::from(Span::styled(
+                "(no request body)",
+                Style::default().fg(Color::DarkGray),
+            ))],
+        }
+    }
+}
+
+const HEX_DUMP_MAX_BYTES: usize = 4096;
+
+#[derive(Debug)]
+struct ResponsePreview {
+    response_info: Option<ResponseInfo>,
+}
+
+impl ResponsePreview {
+    pub fn init(app: &App) -> Self {
+        Self {
+            response_info: app
+                .selected_entry_index()
+                .and_then(|index| app.har.to_response_info(index)),
+        }
+    }
+
+    fn metadata_line(response_info: &ResponseInfo) -> Line<'static> {
+        let label = |s: &str| Span::styled(s.to_string(), Style::default().fg(Color::LightCyan));
+        let bytes = |size: Option<i64>| match size {
+            Some(size) if size >= 0 => body::format_bytes(size),
+            _ => "---".to_string(),
+        };
+
+        let mut spans = vec![
+            label("Size "),
+            Span::raw(bytes(response_info.size)),
+            Span::raw("  "),
+            label("Compression "),
+            Span::raw(bytes(response_info.compression)),
+            Span::raw("  "),
+            label("MIME "),
+            Span::raw(if response_info.mime_type.is_empty() {
+                "---".to_string()
+            } else {
+                response_info.mime_type.clone()
+            }),
+        ];
+        if let Some(encoding) = &response_info.encoding {
+            spans.extend([
+                Span::raw("  "),
+                label("Encoding "),
+                Span::raw(encoding.clone()),
+            ]);
+        }
+
+        Line::from(spans)
+    }
+
+    fn lines(response_info: &ResponseInfo, width: u16) -> Vec<Line<'static>> {
+        let mut lines = vec![Self::metadata_line(response_info), Line::default()];
+
+        match &response_info.body {
+            ResponseBody::Empty => lines.push(Line::from(Span::styled(
+                "(no response body)",
+                Style::default().fg(Color::DarkGray),
+            ))),
+            ResponseBody::Json(text) | ResponseBody::Markup(text) | ResponseBody::Text(text) => {
+                lines.extend(text_lines(text, width))
+            }
+            ResponseBody::Binary(bytes) => {
+                lines.push(title_line(format!(
+                    "Binary ({})",
+                    body::format_bytes(bytes.len() as i64)
+                )));
+                lines.extend(
+                    body::hex_dump(&bytes[..bytes.len().min(HEX_DUMP_MAX_BYTES)])
+                        .into_iter()
+                        .map(|line| {
+                            Line::from(format!("{}{}", " ".repeat(KEY_VALUE_INDENT), line))
+                        }),
+                );
+                if bytes.len() > HEX_DUMP_MAX_BYTES {
+                    lines.push(Line::from(Span::styled(
+                        format!(
+                            "{}... {} more bytes",
+                            " ".repeat(KEY_VALUE_INDENT),
+                            bytes.len() - HEX_DUMP_MAX_BYTES
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+            }
+            ResponseBody::DecodeError(err) => lines.push(Line::from(Span::styled(
+                format!("failed to decode base64 body: {}", err),
+                Style::default().fg(Color::LightRed),
+            ))),
+        }
+
+        lines
+    }
+}
+
+impl PreviewTab for ResponsePreview {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match &self.response_info {
+            Some(response_info) => Self::lines(response_info, width),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TimingPreview {
+    timing_info: Option<TimingInfo>,
+}
+
+impl TimingPreview {
+    pub fn init(app: &App) -> Self {
+        Self {
+            timing_info: app
+                .selected_entry_index()
+                .and_then(|index| app.har.to_timing_info(index)),
+        }
+    }
+
+    fn lines(timing_info: &TimingInfo, width: u16) -> Vec<Line<'static>> {
+        const LABEL_WIDTH: usize = 10;
+        const DURATION_WIDTH: usize = 12;
+
+        let mut lines = key_value_lines(
+            &[
+                (
+                    "Started".to_string(),
+                    match timing_info.started_date_time {
+                        Some(started) => started.format("%Y-%m-%d %H:%M:%S%.3f %:z").to_string(),
+                        None => timing_info.started_date_time_raw.clone(),
+                    },
+                ),
+                ("Total".to_string(), format_millis(timing_info.time)),
+            ],
+            width,
+        );
+        lines.push(Line::default());
+
+        // SSL is a part of Connect, so it is not added to the total length of the bars
+        let total: f64 = timing_info
+            .phases
+            .iter()
+            .filter(|(phase, _)| *phase != TimingPhase::Ssl)
+            .filter_map(|(_, duration)| *duration)
+            .sum();
+        let bar_width = (width as usize)
+            .saturating_sub(KEY_VALUE_INDENT + LABEL_WIDTH + DURATION_WIDTH + 2)
+            .max(10);
+        let scale = |millis: f64| {
+            if total > 0.0 {
+                ((millis / total) * bar_width as f64).round() as usize
+            } else {
+                0
+            }
+        };
+
+        let mut elapsed: f64 = 0.0;
+        for (phase, duration) in &timing_info.phases {
+            let label = Span::styled(
+                format!(
+                    "{}{:<width$}",
+                    " ".repeat(KEY_VALUE_INDENT),
+                    phase.to_string(),
+                    width = LABEL_WIDTH
+                ),
+                Style::default().fg(Color::LightCyan),
+            );
+
+            let Some(duration) = duration else {
+                lines.push(Line::from(vec![
+                    label,
+                    Span::styled("n/a", Style::default().fg(Color::DarkGray)),
+                ]));
+                continue;
+            };
+
+            let start = if *phase == TimingPhase::Ssl {
+                elapsed - duration
+            } else {
+                elapsed
+            };
+            if *phase != TimingPhase::Ssl {
+                elapsed += duration;
+            }
+
+            let offset = scale(start.max(0.0));
+            let length = scale(*duration).max(if *duration > 0.0 { 1 } else { 0 });
+            let length = length.min(bar_width.saturating_sub(offset).max(1));
+
+            lines.push(Line::from(vec![
+                label,
+                Span::raw(" ".repeat(offset)),
+                Span::styled(
+                    "█".repeat(length),
+                    Style::default().fg(timing_phase_color(*phase)),
+                ),
+                Span::raw(" ".repeat(bar_width.saturating_sub(offset + length) + 2)),
+                Span::raw(format!(
+                    "{:>width$}",
+                    format_millis(*duration),
+                    width = DURATION_WIDTH
+                )),
+            ]));
+        }
+
+        lines
+    }
+}
+
+impl PreviewTab for TimingPreview {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match &self.timing_info {
+            Some(timing_info) => Self::lines(timing_info, width),
+            None => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SecurityPreview {
+    connection_info: Option<ConnectionInfo>,
+}
+
+impl SecurityPreview {
+    pub fn init(app: &App) -> Self {
+        Self {
+            connection_info: app
+                .selected_entry_index()
+                .and_then(|index| app.har.to_connection_info(index)),
+        }
+    }
+
+    fn lines(connection_info: &ConnectionInfo, width: u16) -> Vec<Line<'static>> {
+        let or_none = |value: &Option<String>| value.clone().unwrap_or("---".to_string());
+        let mut lines = vec![title_line("Connection".to_string())];
+
+        lines.extend(key_value_lines(
+            &[
+                (
+                    "Server IP".to_string(),
+                    or_none(&connection_info.server_ip_address),
+                ),
+                (
+                    "Connection ID".to_string(),
+                    or_none(&connection_info.connection),
+                ),
+            ],
+            width,
+        ));
+
+        let state = connection_info.security_state.clone();
+        let state_style = match state.as_deref() {
+            Some("secure") => Style::default().fg(Color::LightGreen).bold(),
+            Some("insecure") | Some("broken") => Style::default().fg(Color::LightRed).bold(),
+            Some("weak") => Style::default().fg(Color::LightYellow).bold(),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        lines.push(Line::from(vec![
+            key_span("Security State", KEY_MIN_WIDTH),
+            Span::styled(state.unwrap_or("---".to_string()), state_style),
+        ]));
+
+        if !connection_info.has_tls_details() {
+            return lines;
+        }
+
+        let mut tls = vec![("Protocol".to_string(), or_none(&connection_info.protocol))];
+        tls.push(("Cipher".to_string(), or_none(&connection_info.cipher)));
+        if let Some(key_exchange) = &connection_info.key_exchange {
+            tls.push(("Key Exchange".to_string(), key_exchange.clone()));
+        }
+        if let Some(signature_scheme) = &connection_info.signature_scheme {
+            tls.push(("Signature Scheme".to_string(), signature_scheme.clone()));
+        }
+        lines.push(Line::default());
+        lines.push(title_line("TLS".to_string()));
+        lines.extend(key_value_lines(&tls, width));
+
+        let mut certificate = vec![
+            ("Subject".to_string(), or_none(&connection_info.subject)),
+            ("Issuer".to_string(), or_none(&connection_info.issuer)),
+            (
+                "Valid From".to_string(),
+                or_none(&connection_info.valid_from),
+            ),
+            ("Valid To".to_string(), or_none(&connection_info.valid_to)),
+        ];
+        if !connection_info.san_list.is_empty() {
+            certificate.insert(1, ("SAN".to_string(), connection_info.san_list.join(", ")));
+        }
+        lines.push(Line::default());
+        lines.push(title_line("Certificate".to_string()));
+        lines.extend(key_value_lines(&certificate, width));
+
+        lines
+    }
+}
+
+impl PreviewTab for SecurityPreview {
+    fn to_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match &self.connection_info {
+            Some(connection_info) => Self::lines(connection_info, width),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn timing_phase_color(phase: TimingPhase) -> Color {
+    match phase {
+        TimingPhase::Blocked => Color::DarkGray,
+        TimingPhase::Dns => Color::LightCyan,
+        TimingPhase::Connect => Color::LightYellow,
+        TimingPhase::Ssl => Color::LightMagenta,
+        TimingPhase::Send => Color::LightBlue,
+        TimingPhase::Wait => Color::LightGreen,
+        TimingPhase::Receive => Color::Blue,
+    }
+}
+
+fn format_millis(millis: f64) -> String {
+    format!("{:.2} ms", millis)
+}
+
+fn format_expires(expires: &str) -> String {
+    match chrono::DateTime::parse_from_rfc3339(expires) {
+        Ok(datetime) => datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+        Err(_) => expires.to_string(),
+    }
+}
+
+fn cache_lines(cache: &har::Cache, width: u16) -> Vec<Line<'static>> {
+    let entries = [
+        ("Before Request", &cache.before_request),
+        ("After Request", &cache.after_request),
+    ];
+    if entries.iter().all(|(_, entry)| entry.is_none()) {
+        return vec![none_line()];
+    }
+
+    let mut lines = Vec::new();
+    for (title, entry) in entries {
+        let Some(entry) = entry else {
+            continue;
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{}{}", " ".repeat(KEY_VALUE_GAP), title),
+            Style::default().bold(),
+        )));
+        lines.extend(key_value_lines(
+            &[
+                (
+                    "Expires".to_string(),
+                    entry.expires.clone().unwrap_or("---".to_string()),
+                ),
+                ("Last Access".to_string(), entry.last_access.clone()),
+                ("ETag".to_string(), entry.e_tag.clone()),
+                ("Hit Count".to_string(), entry.hit_count.to_string()),
+            ],
+            width,
+        ));
+    }
+
+    lines
+}
+
+fn finding_lines(findings: &[Finding]) -> Vec<Line<'static>> {
+    if findings.is_empty() {
+        return vec![none_line()];
+    }
+
+    table_lines(
+        &["Rule", "Found In", "Value", "Why"],
+        findings
+            .iter()
+            .map(|finding| {
+                vec![
+                    Span::styled(
+                        finding.rule.to_string(),
+                        Style::default().fg(Color::LightMagenta),
+                    ),
+                    Span::raw(finding.location.clone()),
+                    Span::styled(finding.value.clone(), sensitive_style()),
+                    Span::styled(
+                        finding.rule.description(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]
+            })
+            .collect(),
+    )
+}
+
+fn general_lines(header_info: &HeaderInfo, width: u16) -> Vec<Line<'static>> {
+    let mut lines = key_value_lines(
+        &[
+            ("Request URL".to_string(), header_info.url.to_string()),
+            ("Request Method".to_string(), header_info.method.clone()),
+        ],
+        width,
+    );
+
+    lines.push(Line::from(vec![
+        key_span("Status Code", KEY_MIN_WIDTH),
+        app::status_span(u16::try_from(header_info.status).unwrap_or(0)),
+        Span::raw(format!(" {}", header_info.status_text)),
+    ]));
+
+    lines.extend(key_value_lines(
+        &[
+            ("HTTP Version".to_string(), header_info.http_version.clone()),
+            (
+                "Referrer Policy".to_string(),
+                header_info
+                    .referrer_policy
+                    .clone()
+                    .unwrap_or("---".to_string()),
+            ),
+        ],
+        width,
+    ));
+
+    lines
+}
+
+fn title_line(title: String) -> Line<'static> {
+    Line::from(Span::styled(title, Style::default().bold()))
+}
+
+fn none_line() -> Line<'static> {
+    Line::from(Span::styled(
+        format!("{}(none)", " ".repeat(KEY_VALUE_INDENT)),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+const COLUMN_MAX_WIDTH: usize = 40;
+
+/// Lays out the rows as a table of aligned columns, dropping columns that are empty in every row.
+/// Only the last column may grow beyond `COLUMN_MAX_WIDTH`.
+fn table_lines(columns: &[&str], rows: Vec<Vec<Span<'static>>>) -> Vec<Line<'static>> {
+    let widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let content_width = rows
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.content.chars().count())
+                .max()
+                .unwrap_or(0);
+            if content_width == 0 {
+                0
+            } else if i + 1 == columns.len() {
+                content_width.max(column.len())
+            } else {
+                content_width.max(column.len()).min(COLUMN_MAX_WIDTH)
+            }
+        })
+        .collect();
+
+    let indent = Span::raw(" ".repeat(KEY_VALUE_INDENT));
+    let header = Line::from(
+        std::iter::once(indent.clone())
+            .chain(
+                columns
+                    .iter()
+                    .zip(widths.iter())
+                    .filter(|(_, width)| **width > 0)
+                    .map(|(column, width)| {
+                        Span::styled(
+                            format!("{:<width$}", column, width = width),
+                            Style::default().bold().underlined(),
+                        )
+                    })
+                    .flat_map(|cell| [cell, Span::raw("  ")]),
+            )
+            .collect::<Vec<Span>>(),
+    );
+
+    std::iter::once(header)
+        .chain(rows.into_iter().map(|row| {
+            Line::from(
+                std::iter::once(indent.clone())
+                    .chain(
+                        row.into_iter()
+                            .zip(widths.iter())
+                            .filter(|(_, width)| **width > 0)
+                            .map(|(cell, width)| {
+                                Span::styled(
+                                    format!(
+                                        "{:<width$}",
+                                        truncate(&cell.content, *width),
+                                        width = width
+                                    ),
+                                    cell.style,
+                                )
+                            })
+                            .flat_map(|cell| [cell, Span::raw("  ")]),
+                    )
+                    .collect::<Vec<Span>>(),
+            )
+        }))
+        .collect()
+}
+
+fn truncate(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        s.to_string()
+    } else {
+        let mut truncated: String = s.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+const KEY_VALUE_INDENT: usize = 4;
+const KEY_VALUE_GAP: usize = 2;
+const KEY_MIN_WIDTH: usize = 15;
+const KEY_MAX_WIDTH: usize = 32;
+
+fn key_span(key: &str, key_width: usize) -> Span<'static> {
+    Span::styled(
+        format!(
+            "{}{:<width$}",
+            " ".repeat(KEY_VALUE_INDENT),
+            key,
+            width = key_width + KEY_VALUE_GAP
+        ),
+        Style::default().fg(Color::LightCyan),
+    )
+}
+
+fn key_value_lines(pairs: &[(String, String)], width: u16) -> Vec<Line<'static>> {
+    let key_width = pairs
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp(KEY_MIN_WIDTH, KEY_MAX_WIDTH);
+    let value_width = (width as usize)
+        .saturating_sub(KEY_VALUE_INDENT + key_width + KEY_VALUE_GAP)
+        .max(16);
+    let padding = " ".repeat(KEY_VALUE_INDENT + key_width + KEY_VALUE_GAP);
+
+    pairs
+        .iter()
+        .flat_map(|(key, value)| {
+            let ranges = detect::find_header_ranges(key, value);
+            let chunks = wrap_highlighted(value, &ranges, value_width);
+            let key_span = key_span(key, key_width);
+
+            chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, chunk)| {
+                    let head = if i == 0 {
+                        key_span.clone()
+                    } else {
+                        Span::raw(padding.clone())
+                    };
+                    Line::from(std::iter::once(head).chain(chunk).collect::<Vec<Span>>())
+                })
+                .collect::<Vec<Line>>()
+        })
+        .collect()
+}
+
+fn text_lines(text: &str, width: u16) -> Vec<Line<'static>> {
+    let indent = " ".repeat(KEY_VALUE_INDENT);
+    let text_width = (width as usize).saturating_sub(KEY_VALUE_INDENT).max(16);
+
+    text.lines()
+        .flat_map(|line| {
+            let line = line.replace('\t', "    ");
+            wrap_highlighted(&line, &detect::find_ranges(&line), text_width)
+        })
+        .map(|chunk| {
+            Line::from(
+                std::iter::once(Span::raw(indent.clone()))
+                    .chain(chunk)
+                    .collect::<Vec<Span>>(),
+            )
+        })
+        .collect()
+}
+
+fn sensitive_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::LightMagenta)
+}
+
+/// Wraps the text like `wrap_chars`, styling the given byte ranges as sensitive
+fn wrap_highlighted(
+    s: &str,
+    ranges: &[(usize, usize, detect::Rule)],
+    width: usize,
+) -> Vec<Vec<Span<'static>>> {
+    if ranges.is_empty() {
+        return wrap_chars(s, width)
+            .into_iter()
+            .map(|chunk| vec![Span::raw(chunk)])
+            .collect();
+    }
+
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    chars
+        .chunks(width.max(1))
+        .map(|chunk| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut current = String::new();
+            let mut current_sensitive = false;
+            for (offset, c) in chunk {
+                let sensitive = ranges
+                    .iter()
+                    .any(|(start, end, _)| (*start..*end).contains(offset));
+                if sensitive != current_sensitive && !current.is_empty() {
+                    spans.push(highlighted_span(
+                        std::mem::take(&mut current),
+                        current_sensitive,
+                    ));
+                }
+                current_sensitive = sensitive;
+                current.push(*c);
+            }
+            spans.push(highlighted_span(current, current_sensitive));
+            spans
+        })
+        .collect()
+}
+
+fn highlighted_span(text: String, sensitive: bool) -> Span<'static> {
+    if sensitive {
+        Span::styled(text, sensitive_style())
+    } else {
+        Span::raw(text)
+    }
+}
+
+fn wrap_chars(s: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+
+    chars
+        .chunks(width.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/// Renders `harview diff --tui`: the compared entries above, and the selected ones side by side
+pub fn render_diff(app: &mut DiffApp, frame: &mut Frame) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(40),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+
+    // Borders and the header row are not part of a page
+    app.set_table_page_size(layout[0].height.saturating_sub(3) as usize);
+    let mut state = TableState::default().with_selected(Some(app.get_index()));
+    DiffTable::init(app).render(layout[0], frame.buffer_mut(), &mut state);
+
+    app.detail_area = layout[1];
+    DiffDetail::init(app).render(layout[1], frame.buffer_mut());
+    DiffStatusBar::init(app).render(layout[2], frame.buffer_mut());
+}
+
+fn change_style(change: Change) -> Style {
+    match change {
+        Change::Added => Style::default().fg(Color::LightGreen).bold(),
+        Change::Removed => Style::default().fg(Color::LightRed).bold(),
+        Change::Changed => Style::default().fg(Color::Yellow).bold(),
+        Change::Unchanged => Style::default().fg(Color::DarkGray),
+    }
+}
+
+/// Style of the values that differ between the captures
+fn differs_style() -> Style {
+    Style::default().fg(Color::Black).bg(Color::Yellow)
+}
+
+#[derive(Debug)]
+struct DiffTable {
+    rows: Vec<EntryDiff>,
+    title: String,
+}
+
+impl DiffTable {
+    pub fn init(app: &DiffApp) -> Self {
+        Self {
+            rows: app.rows().into_iter().cloned().collect(),
+            title: format!(" {} → {} ", app.paths[0], app.paths[1]),
+        }
+    }
+
+    fn row(diff: &EntryDiff) -> Row<'static> {
+        let changed = |changed: bool| {
+            if changed {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            }
+        };
+
+        let (status, size, time) = match (&diff.before, &diff.after) {
+            (Some(before), Some(after)) => (
+                if diff.status_changed() {
+                    Span::styled(
+                        format!("{} → {}", before.status, after.status),
+                        changed(true),
+                    )
+                } else {
+                    app::status_span(u16::try_from(after.status).unwrap_or(0))
+                },
+                match diff.size_delta {
+                    Some(delta) if delta != 0 => {
+                        Span::styled(diff::format_size_delta(delta), changed(true))
+                    }
+                    _ => Span::raw(after.size.map(body::format_bytes).unwrap_or_default()),
+                },
+                match diff.time_delta {
+                    Some(delta) => Span::raw(diff::format_time_delta(delta)),
+                    None => Span::raw(""),
+                },
+            ),
+            (Some(side), None) | (None, Some(side)) => (
+                app::status_span(u16::try_from(side.status).unwrap_or(0)),
+                Span::raw(side.size.map(body::format_bytes).unwrap_or_default()),
+                Span::raw(format!("{:.0} ms", side.time)),
+            ),
+            (None, None) => (Span::raw(""), Span::raw(""), Span::raw("")),
+        };
+
+        Row::new([
+            Cell::new(Span::styled(
+                diff.change.symbol(),
+                change_style(diff.change),
+            )),
+            Cell::new(Span::styled(
+                diff.method.clone(),
+                Style::default().fg(Color::White).bold(),
+            )),
+            Cell::new(diff.display_url().to_string()),
+            Cell::new(status),
+            Cell::new(Line::from(size).right_aligned()),
+            Cell::new(Line::from(time).right_aligned()),
+        ])
+    }
+}
+
+impl StatefulWidget for DiffTable {
+    type State = TableState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let headers = Row::new(vec![
+            Cell::from(" "),
+            Cell::from("Method"),
+            Cell::from("URL"),
+            Cell::from("Status"),
+            Cell::from(Line::from("Size").right_aligned()),
+            Cell::from(Line::from("Time").right_aligned()),
+        ])
+        .style(Style::default().bold().underlined());
+        let widths = [
+            Constraint::Length(1),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ];
+        let rows: Vec<Row> = self.rows.iter().map(Self::row).collect();
+
+        let table = Table::new(rows, widths)
+            .header(headers)
+            .highlight_style(Style::default().reversed())
+            .block(
+                Block::default()
+                    .title(self.title)
+                    .padding(Padding::horizontal(1))
+                    .borders(Borders::ALL)
+                    .border_style(border_style(true)),
+            );
+        StatefulWidget::render(table, area, buf, state);
+    }
+}
+
+#[derive(Debug)]
+struct DiffDetail {
+    paths: [String; 2],
+    /// Entry numbers of the selected entries in each file
+    entries: [Option<usize>; 2],
+    sections: Vec<(&'static str, Vec<ComparedField>)>,
+    scroll: usize,
+}
+
+impl DiffDetail {
+    pub fn init(app: &DiffApp) -> Self {
+        let selected = app.selected();
+        Self {
+            paths: app.paths.clone(),
+            entries: [
+                selected.and_then(|diff| Some(diff.before.as_ref()?.entry)),
+                selected.and_then(|diff| Some(diff.after.as_ref()?.entry)),
+            ],
+            sections: app.compared_fields(),
+            scroll: app.detail_scroll,
+        }
+    }
+
+    /// Lines of one side. Both sides have a line for each line of a field, so they stay aligned.
+    fn lines(&self, after: bool, width: u16) -> Vec<Line<'static>> {
+        let key_width = self
+            .sections
+            .iter()
+            .flat_map(|(_, fields)| fields.iter().map(|field| field.name.chars().count()))
+            .max()
+            .unwrap_or(0)
+            .clamp(KEY_MIN_WIDTH, KEY_MAX_WIDTH);
+        let value_width = (width as usize)
+            .saturating_sub(KEY_VALUE_INDENT + key_width + KEY_VALUE_GAP)
+            .max(8);
+        let padding = " ".repeat(KEY_VALUE_INDENT + key_width + KEY_VALUE_GAP);
+
+        let mut lines = Vec::new();
+        for (title, fields) in &self.sections {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(title_line(title.to_string()));
+            if fields.is_empty() {
+                lines.push(none_line());
+            }
+            for field in fields {
+                let value = if after { &field.after } else { &field.before };
+                let other = if after { &field.before } else { &field.after };
+                let height = value
+                    .iter()
+                    .chain(other)
+                    .map(|value| value.lines().count().max(1))
+                    .max()
+                    .unwrap_or(1);
+                let value_lines: Vec<&str> = value
+                    .as_deref()
+                    .map(|value| value.lines().collect())
+                    .unwrap_or_default();
+
+                for i in 0..height {
+                    let key = if i == 0 {
+                        key_span(&truncate(&field.name, key_width), key_width)
+                    } else {
+                        Span::raw(padding.clone())
+                    };
+                    let value = match (value, value_lines.get(i)) {
+                        (None, _) if i == 0 => {
+                            Span::styled("---", Style::default().fg(Color::DarkGray))
+                        }
+                        (_, Some(line)) => Span::styled(
+                            truncate(line, value_width),
+                            if field.differs {
+                                differs_style()
+                            } else {
+                                Style::default()
+                            },
+                        ),
+                        _ => Span::raw(""),
+                    };
+                    lines.push(Line::from(vec![key, value]));
+                }
+            }
+        }
+        lines
+    }
+
+    fn render_side(&self, after: bool, area: Rect, buf: &mut Buffer) {
+        let side = usize::from(after);
+        let title = match self.entries[side] {
+            Some(entry) => format!(" {} #{} ", self.paths[side], entry),
+            None => format!(" {} ", self.paths[side]),
+        };
+        let block = Block::default()
+            .title(title)
+            .padding(Padding::horizontal(1))
+            .borders(Borders::ALL)
+            .border_style(border_style(false));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let lines = match self.entries[side] {
+            Some(_) => self.lines(after, inner.width),
+            None if self.sections.is_empty() => Vec::new(),
+            None => vec![Line::from(Span::styled(
+                format!("Not in {}", self.paths[side]),
+                Style::default().fg(Color::DarkGray),
+            ))],
+        };
+        Widget::render(scrolled_paragraph(lines, self.scroll), inner, buf);
+    }
+}
+
+impl Widget for DiffDetail {
     fn render(self, area: Rect, buf: &mut Buffer)
     where
         Self: Sized,
     {
-        if let Some(header_info) = self.header_info {
-            todo!();
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        self.render_side(false, layout[0], buf);
+        self.render_side(true, layout[1], buf);
+    }
+}
+
+#[derive(Debug)]
+struct DiffStatusBar {
+    counts: Vec<(Change, usize)>,
+    show_unchanged: bool,
+    position: (usize, usize),
+}
+
+impl DiffStatusBar {
+    pub fn init(app: &DiffApp) -> Self {
+        let rows = app.rows().len();
+        Self {
+            counts: [
+                Change::Added,
+                Change::Removed,
+                Change::Changed,
+                Change::Unchanged,
+            ]
+            .into_iter()
+            .map(|change| (change, app.count(change)))
+            .collect(),
+            show_unchanged: app.show_unchanged,
+            position: ((app.get_index() + 1).min(rows), rows),
         }
     }
 }
+
+impl Widget for DiffStatusBar {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let left: Vec<Span> = self
+            .counts
+            .iter()
+            .map(|(change, count)| {
+                Span::styled(
+                    format!("{}{} {:?}  ", change.symbol(), count, change).to_lowercase(),
+                    change_style(*change),
+                )
+            })
+            .collect();
+
+        let (position, rows) = self.position;
+        let right = Line::from(vec![
+            Span::styled(
+                if self.show_unchanged {
+                    "a: hide unchanged  "
+                } else {
+                    "a: show unchanged  "
+                },
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                "J/K: scroll details  q: quit  ",
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{}/{} ", position, rows),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+        let layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(right.width() as u16),
+            ])
+            .split(area);
+
+        Widget::render(Paragraph::new(Line::from(left)), layout[0], buf);
+        Widget::render(Paragraph::new(right), layout[1], buf);
+    }
+}